@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Config {
@@ -28,14 +29,141 @@ pub struct Config {
 
     #[serde(default)]
     pub additional_imports: Vec<ImportConfig>,
+
+    /// Path alias pointing at the output directory, such as "@/generated";
+    /// imports between generated files are relative when unset
+    #[serde(default)]
+    pub import_alias: Option<String>,
+
+    /// How enums are emitted: "string" (display names), "numeric" (C# values)
+    /// or "union" (a string union type)
+    #[serde(default)]
+    pub enum_style: EnumStyle,
+
+    /// How single-field structs such as strongly typed IDs are emitted:
+    /// "object", "unwrap" (the inner type's schema) or "brand"
+    #[serde(default)]
+    pub wrapper_structs: WrapperStructStyle,
+
+    /// Whether C# interfaces also get a Zod schema next to their TS interface
+    #[serde(default)]
+    pub interface_schemas: bool,
+
+    /// Where generated files go: "directory" mirrors the source tree,
+    /// "namespace" derives folders from the C# namespace
+    #[serde(default)]
+    pub output_layout: OutputLayout,
+
+    /// Namespace prefix left out of namespace-based folders, such as
+    /// "UniTrack.Contracts"
+    #[serde(default)]
+    pub namespace_prefix: Option<String>,
+
+    /// Preprocessor symbols treated as defined, e.g. ["DEBUG"]; code in
+    /// inactive `#if` branches is ignored
+    #[serde(default)]
+    pub defined_symbols: Vec<String>,
+
+    /// Leave out `[Obsolete]` types and members instead of marking them
+    /// `@deprecated`
+    #[serde(default)]
+    pub drop_obsolete: bool,
+
+    /// Hand-written schemas for C# types, keyed by type name such as "Money",
+    /// "NodaTime.Instant" or "Result<T>"; these win over the built-in mapping
+    #[serde(default)]
+    pub type_mappings: BTreeMap<String, TypeMapping>,
+
+    /// How `long`, `ulong` and `decimal` values are represented
+    #[serde(default)]
+    pub precision: PrecisionConfig,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum EnumStyle {
+    #[default]
+    String,
+    Numeric,
+    /// A union of the display-name strings instead of a TypeScript enum
+    Union,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum WrapperStructStyle {
+    #[default]
+    Object,
+    Unwrap,
+    Brand,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputLayout {
+    #[default]
+    Directory,
+    Namespace,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct ImportConfig {
     pub name: String,
     pub path: String,
 }
 
+/// Representation of numbers a JavaScript `number` can't hold exactly
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Precision {
+    /// A plain `number`, accepting the loss of precision
+    #[default]
+    Number,
+    /// A `bigint`, for integers only (`z.bigint()`)
+    Bigint,
+    /// A `bigint` parsed from the number or string in the JSON
+    /// (`z.coerce.bigint()`), for integers only
+    CoercedBigint,
+    /// A string of digits validated with a regex
+    String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct PrecisionConfig {
+    /// Applies to `long` and `ulong`
+    #[serde(default)]
+    pub long: Precision,
+
+    #[serde(default)]
+    pub decimal: Precision,
+
+    /// Overrides for single properties, keyed "Type.Property"
+    #[serde(default)]
+    pub properties: BTreeMap<String, Precision>,
+}
+
+impl PrecisionConfig {
+    /// Precision for the 64-bit integers and the decimals in a property's type
+    pub fn for_property(&self, type_name: &str, property: &str) -> (Precision, Precision) {
+        match self.properties.get(&format!("{}.{}", type_name, property)) {
+            Some(&precision) => (precision, precision),
+            None => (self.long, self.decimal),
+        }
+    }
+}
+
+/// Zod expression and TypeScript type used in place of a C# type. Type
+/// parameters from the key are written as `{T}` in both
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TypeMapping {
+    pub zod: String,
+    pub typescript: String,
+
+    /// Import added to every generated file using the type
+    #[serde(default)]
+    pub import: Option<ImportConfig>,
+}
+
 fn default_extensions() -> Vec<String> {
     vec!["cs".to_string()]
 }
@@ -58,6 +186,16 @@ impl Default for Config {
             localized: false,
             i18n_library: default_i18n_import(),
             additional_imports: default_imports(),
+            import_alias: None,
+            enum_style: EnumStyle::default(),
+            wrapper_structs: WrapperStructStyle::default(),
+            interface_schemas: false,
+            output_layout: OutputLayout::default(),
+            namespace_prefix: None,
+            defined_symbols: Vec::new(),
+            drop_obsolete: false,
+            type_mappings: BTreeMap::new(),
+            precision: PrecisionConfig::default(),
         }
     }
 }
@@ -71,14 +209,14 @@ impl Config {
         Ok(config)
     }
 
-    pub fn is_valid_extension(&self, path: &PathBuf) -> bool {
+    pub fn is_valid_extension(&self, path: &Path) -> bool {
         path.extension()
             .and_then(|ext| ext.to_str())
             .map(|ext| self.extensions.iter().any(|e| e == ext))
             .unwrap_or(false)
     }
 
-    pub fn should_ignore(&self, path: &PathBuf) -> bool {
+    pub fn should_ignore(&self, path: &Path) -> bool {
         self.ignore.iter().any(|pattern| {
             glob::Pattern::new(pattern)
                 .map(|p| p.matches(&path.to_string_lossy()))
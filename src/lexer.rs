@@ -0,0 +1,768 @@
+use std::collections::HashSet;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    Identifier,
+    Number,
+    String,
+    /// `$"..."` string, whose value depends on its holes
+    InterpolatedString,
+    Char,
+    Punct,
+    Eof,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriviaKind {
+    LineComment,
+    BlockComment,
+    DocComment,
+    Directive,
+    /// Source excluded by an inactive `#if` branch
+    DisabledText,
+}
+
+#[derive(Debug, Clone)]
+pub struct Trivia {
+    pub kind: TriviaKind,
+    pub text: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct Token {
+    pub kind: TokenKind,
+    /// Source text, or the decoded value for string and char literals
+    pub text: String,
+    pub start: usize,
+    pub end: usize,
+    pub line: usize,
+    pub column: usize,
+    pub leading_trivia: Vec<Trivia>,
+    /// Nullable annotation context set by `#nullable`, or None where the
+    /// project setting applies
+    pub nullable_annotations: Option<bool>,
+}
+
+impl Token {
+    pub fn is(&self, text: &str) -> bool {
+        matches!(self.kind, TokenKind::Identifier | TokenKind::Punct) && self.text == text
+    }
+
+    pub fn is_identifier(&self) -> bool {
+        self.kind == TokenKind::Identifier
+    }
+}
+
+// `>` is never combined so that `List<List<int>>` closes both type argument
+// lists; the expression parser joins adjacent `>` tokens where needed.
+const PUNCTUATION: &[&str] = &[
+    "??=", "<<=", "::", "??", "?.", "=>", "==", "!=", "<=", "<<", "&&", "||", "++", "--", "->",
+    "+=", "-=", "*=", "/=", "%=", "&=", "|=", "^=", "..",
+];
+
+/// One open `#if` ... `#endif` group
+#[derive(Debug, Clone, Copy)]
+struct Conditional {
+    /// Whether the group's current branch is compiled
+    active: bool,
+    /// Whether any branch of the group has been taken yet
+    taken: bool,
+    /// Whether the enclosing code is compiled
+    enclosing_active: bool,
+}
+
+pub struct Lexer<'a> {
+    source: &'a str,
+    pos: usize,
+    line: usize,
+    line_start: usize,
+    at_line_start: bool,
+    symbols: HashSet<String>,
+    conditionals: Vec<Conditional>,
+    nullable_annotations: Option<bool>,
+}
+
+impl<'a> Lexer<'a> {
+    pub fn new(source: &'a str, defined_symbols: &[String]) -> Self {
+        Self {
+            source,
+            pos: 0,
+            line: 1,
+            line_start: 0,
+            at_line_start: true,
+            symbols: defined_symbols.iter().cloned().collect(),
+            conditionals: Vec::new(),
+            nullable_annotations: None,
+        }
+    }
+
+    pub fn tokenize(mut self) -> Vec<Token> {
+        let mut tokens = Vec::new();
+        loop {
+            let leading_trivia = self.read_trivia();
+            let token = self.read_token(leading_trivia);
+            let done = token.kind == TokenKind::Eof;
+            tokens.push(token);
+            if done {
+                return tokens;
+            }
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.source[self.pos..].chars().next()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.source[self.pos..].chars().nth(offset)
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.source[self.pos..]
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        if c == '\n' {
+            self.line += 1;
+            self.line_start = self.pos;
+            self.at_line_start = true;
+        } else if !c.is_whitespace() {
+            self.at_line_start = false;
+        }
+        Some(c)
+    }
+
+    fn bump_n(&mut self, n: usize) {
+        for _ in 0..n {
+            self.bump();
+        }
+    }
+
+    fn read_line(&mut self) -> &'a str {
+        let start = self.pos;
+        while let Some(c) = self.peek() {
+            if c == '\n' {
+                break;
+            }
+            self.bump();
+        }
+        self.source[start..self.pos].trim_end_matches('\r')
+    }
+
+    fn read_trivia(&mut self) -> Vec<Trivia> {
+        let mut trivia = Vec::new();
+        loop {
+            match self.peek() {
+                Some(c) if c.is_whitespace() => {
+                    self.bump();
+                }
+                Some('#') if self.at_line_start => {
+                    let text = self.read_line().to_string();
+                    self.apply_directive(&text);
+                    trivia.push(Trivia {
+                        kind: TriviaKind::Directive,
+                        text,
+                    });
+                    if !self.is_active() {
+                        trivia.push(Trivia {
+                            kind: TriviaKind::DisabledText,
+                            text: self.skip_disabled_text(),
+                        });
+                    }
+                }
+                // A `///` after code on the same line documents nothing that follows
+                Some('/')
+                    if self.rest().starts_with("///")
+                        && !self.rest().starts_with("////")
+                        && self.at_line_start =>
+                {
+                    self.bump_n(3);
+                    let text = self.read_line().to_string();
+                    trivia.push(Trivia {
+                        kind: TriviaKind::DocComment,
+                        text,
+                    });
+                }
+                Some('/') if self.rest().starts_with("//") => {
+                    self.bump_n(2);
+                    let text = self.read_line().to_string();
+                    trivia.push(Trivia {
+                        kind: TriviaKind::LineComment,
+                        text,
+                    });
+                }
+                Some('/') if self.rest().starts_with("/*") => {
+                    let start = self.pos;
+                    self.bump_n(2);
+                    while !self.rest().is_empty() && !self.rest().starts_with("*/") {
+                        self.bump();
+                    }
+                    self.bump_n(2);
+                    trivia.push(Trivia {
+                        kind: TriviaKind::BlockComment,
+                        text: self.source[start..self.pos].to_string(),
+                    });
+                }
+                _ => return trivia,
+            }
+        }
+    }
+
+    fn is_active(&self) -> bool {
+        self.conditionals.last().is_none_or(|c| c.active)
+    }
+
+    fn apply_directive(&mut self, line: &str) {
+        let directive = line.trim_start().trim_start_matches('#').trim_start();
+        let (name, argument) = directive
+            .split_once(char::is_whitespace)
+            .unwrap_or((directive, ""));
+        let argument = argument.split("//").next().unwrap_or("").trim();
+
+        match name {
+            "if" => {
+                let enclosing_active = self.is_active();
+                let taken = enclosing_active && self.evaluate(argument);
+                self.conditionals.push(Conditional {
+                    active: taken,
+                    taken,
+                    enclosing_active,
+                });
+            }
+            "elif" => {
+                let value = self.evaluate(argument);
+                if let Some(c) = self.conditionals.last_mut() {
+                    c.active = c.enclosing_active && !c.taken && value;
+                    c.taken |= c.active;
+                }
+            }
+            "else" => {
+                if let Some(c) = self.conditionals.last_mut() {
+                    c.active = c.enclosing_active && !c.taken;
+                    c.taken = true;
+                }
+            }
+            "endif" => {
+                self.conditionals.pop();
+            }
+            "define" if self.is_active() => {
+                self.symbols.insert(argument.to_string());
+            }
+            "undef" if self.is_active() => {
+                self.symbols.remove(argument);
+            }
+            // Only the annotation context matters here; `warnings` alone
+            // leaves it unchanged
+            "nullable" if self.is_active() => {
+                let (setting, target) = argument
+                    .split_once(char::is_whitespace)
+                    .map_or((argument, ""), |(s, t)| (s, t.trim()));
+                if target.is_empty() || target == "annotations" {
+                    match setting {
+                        "enable" => self.nullable_annotations = Some(true),
+                        "disable" => self.nullable_annotations = Some(false),
+                        "restore" => self.nullable_annotations = None,
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Skips whole lines up to the next directive that may re-enable code
+    fn skip_disabled_text(&mut self) -> String {
+        let start = self.pos;
+        while !self.rest().is_empty() {
+            let line_end = self.rest().find('\n').map_or(self.rest().len(), |i| i + 1);
+            let directive = self.rest()[..line_end].trim_start();
+            let name = directive
+                .strip_prefix('#')
+                .map(|d| d.trim_start().split(|c: char| !c.is_alphabetic()).next())
+                .unwrap_or_default();
+            if matches!(name, Some("if" | "elif" | "else" | "endif")) {
+                break;
+            }
+            self.bump_n(self.rest()[..line_end].chars().count());
+        }
+        self.source[start..self.pos].to_string()
+    }
+
+    /// Evaluates a conditional expression: symbols, `true`/`false`, `!`,
+    /// `==`, `!=`, `&&`, `||` and parentheses
+    fn evaluate(&self, expression: &str) -> bool {
+        let tokens = condition_tokens(expression);
+        let mut pos = 0;
+        self.evaluate_or(&tokens, &mut pos)
+    }
+
+    fn evaluate_or(&self, tokens: &[String], pos: &mut usize) -> bool {
+        let mut value = self.evaluate_and(tokens, pos);
+        while tokens.get(*pos).is_some_and(|t| t == "||") {
+            *pos += 1;
+            value |= self.evaluate_and(tokens, pos);
+        }
+        value
+    }
+
+    fn evaluate_and(&self, tokens: &[String], pos: &mut usize) -> bool {
+        let mut value = self.evaluate_equality(tokens, pos);
+        while tokens.get(*pos).is_some_and(|t| t == "&&") {
+            *pos += 1;
+            value &= self.evaluate_equality(tokens, pos);
+        }
+        value
+    }
+
+    fn evaluate_equality(&self, tokens: &[String], pos: &mut usize) -> bool {
+        let mut value = self.evaluate_unary(tokens, pos);
+        while let Some(op) = tokens.get(*pos).filter(|t| *t == "==" || *t == "!=") {
+            let equal = op == "==";
+            *pos += 1;
+            value = (value == self.evaluate_unary(tokens, pos)) == equal;
+        }
+        value
+    }
+
+    fn evaluate_unary(&self, tokens: &[String], pos: &mut usize) -> bool {
+        let Some(token) = tokens.get(*pos) else {
+            return false;
+        };
+        *pos += 1;
+        match token.as_str() {
+            "!" => !self.evaluate_unary(tokens, pos),
+            "(" => {
+                let value = self.evaluate_or(tokens, pos);
+                if tokens.get(*pos).is_some_and(|t| t == ")") {
+                    *pos += 1;
+                }
+                value
+            }
+            "true" => true,
+            "false" => false,
+            symbol => self.symbols.contains(symbol),
+        }
+    }
+
+    fn read_token(&mut self, leading_trivia: Vec<Trivia>) -> Token {
+        let start = self.pos;
+        let line = self.line;
+        let column = self.source[self.line_start..start].chars().count() + 1;
+
+        let (kind, text) = match self.peek() {
+            None => (TokenKind::Eof, String::new()),
+            Some(c) if c.is_alphabetic() || c == '_' => (TokenKind::Identifier, self.read_word()),
+            Some('@')
+                if self
+                    .peek_at(1)
+                    .is_some_and(|c| c.is_alphabetic() || c == '_') =>
+            {
+                self.bump();
+                (TokenKind::Identifier, self.read_word())
+            }
+            Some(c) if c.is_ascii_digit() => (TokenKind::Number, self.read_number()),
+            Some('.') if self.peek_at(1).is_some_and(|c| c.is_ascii_digit()) => {
+                (TokenKind::Number, self.read_number())
+            }
+            Some('\'') => (TokenKind::Char, self.read_char()),
+            Some('"') | Some('@') | Some('$') if self.at_string_start() => {
+                let kind = if self.rest().trim_start_matches('@').starts_with('$') {
+                    TokenKind::InterpolatedString
+                } else {
+                    TokenKind::String
+                };
+                (kind, self.read_string())
+            }
+            Some(c) => {
+                let text = PUNCTUATION
+                    .iter()
+                    .find(|p| self.rest().starts_with(**p))
+                    .map(|p| p.to_string())
+                    .unwrap_or_else(|| c.to_string());
+                self.bump_n(text.chars().count());
+                (TokenKind::Punct, text)
+            }
+        };
+
+        Token {
+            kind,
+            text,
+            start,
+            end: self.pos,
+            line,
+            column,
+            leading_trivia,
+            nullable_annotations: self.nullable_annotations,
+        }
+    }
+
+    fn read_word(&mut self) -> String {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_alphanumeric() || c == '_') {
+            self.bump();
+        }
+        self.source[start..self.pos].to_string()
+    }
+
+    fn read_number(&mut self) -> String {
+        let start = self.pos;
+        if self.rest().starts_with("0x")
+            || self.rest().starts_with("0X")
+            || self.rest().starts_with("0b")
+            || self.rest().starts_with("0B")
+        {
+            self.bump_n(2);
+        }
+        while let Some(c) = self.peek() {
+            let exponent_sign = (c == '+' || c == '-')
+                && self.source[start..self.pos].ends_with(['e', 'E'])
+                && !self.source[start..self.pos].starts_with("0x");
+            let fraction = c == '.' && self.peek_at(1).is_some_and(|c| c.is_ascii_digit());
+            if c.is_ascii_alphanumeric() || c == '_' || fraction || exponent_sign {
+                self.bump();
+            } else {
+                break;
+            }
+        }
+        self.source[start..self.pos].to_string()
+    }
+
+    fn read_char(&mut self) -> String {
+        self.bump();
+        let mut value = String::new();
+        while let Some(c) = self.bump() {
+            match c {
+                '\'' | '\n' => break,
+                '\\' => value.push(self.read_escape()),
+                c => value.push(c),
+            }
+        }
+        value
+    }
+
+    fn at_string_start(&self) -> bool {
+        let prefix: String = self
+            .rest()
+            .chars()
+            .take_while(|c| *c == '@' || *c == '$')
+            .collect();
+        let valid_prefix = prefix.matches('@').count() <= 1;
+        valid_prefix && self.rest()[prefix.len()..].starts_with('"')
+    }
+
+    fn read_string(&mut self) -> String {
+        let mut verbatim = false;
+        let mut interpolation_depth = 0;
+        while let Some(c) = self.peek() {
+            match c {
+                '@' => verbatim = true,
+                '$' => interpolation_depth += 1,
+                _ => break,
+            }
+            self.bump();
+        }
+
+        let quotes = self.rest().chars().take_while(|c| *c == '"').count();
+        if quotes >= 3 {
+            return self.read_raw_string(quotes);
+        }
+
+        self.bump();
+        let mut value = String::new();
+        while let Some(c) = self.bump() {
+            match c {
+                '"' if verbatim && self.peek() == Some('"') => {
+                    self.bump();
+                    value.push('"');
+                }
+                '"' => break,
+                '\\' if !verbatim => value.push(self.read_escape()),
+                '\n' if !verbatim => break,
+                '{' if interpolation_depth > 0 && self.peek() == Some('{') => {
+                    self.bump();
+                    value.push('{');
+                }
+                '{' if interpolation_depth > 0 => {
+                    value.push('{');
+                    value.push_str(&self.read_interpolation());
+                    value.push('}');
+                }
+                c => value.push(c),
+            }
+        }
+        value
+    }
+
+    fn read_raw_string(&mut self, quotes: usize) -> String {
+        let delimiter = "\"".repeat(quotes);
+        self.bump_n(quotes);
+        let start = self.pos;
+        while !self.rest().is_empty() && !self.rest().starts_with(&delimiter) {
+            self.bump();
+        }
+        let raw = &self.source[start..self.pos];
+        self.bump_n(quotes);
+
+        // Multi-line raw strings drop the first and last line and the
+        // indentation of the closing delimiter
+        if let Some(body) = raw.strip_prefix('\n').or_else(|| raw.strip_prefix("\r\n")) {
+            let (body, indent) = body.rsplit_once('\n').unwrap_or((body, ""));
+            return body
+                .lines()
+                .map(|line| line.strip_prefix(indent).unwrap_or(line))
+                .collect::<Vec<_>>()
+                .join("\n");
+        }
+        raw.to_string()
+    }
+
+    fn read_interpolation(&mut self) -> String {
+        let start = self.pos;
+        let mut depth = 1;
+        while let Some(c) = self.peek() {
+            match c {
+                '{' => depth += 1,
+                '}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        let text = self.source[start..self.pos].to_string();
+                        self.bump();
+                        return text;
+                    }
+                }
+                '"' | '@' | '$' if self.at_string_start() => {
+                    self.read_string();
+                    continue;
+                }
+                '\'' => {
+                    self.read_char();
+                    continue;
+                }
+                _ => {}
+            }
+            self.bump();
+        }
+        self.source[start..self.pos].to_string()
+    }
+
+    fn read_escape(&mut self) -> char {
+        match self.bump() {
+            Some('n') => '\n',
+            Some('r') => '\r',
+            Some('t') => '\t',
+            Some('0') => '\0',
+            Some('a') => '\u{7}',
+            Some('b') => '\u{8}',
+            Some('f') => '\u{c}',
+            Some('v') => '\u{b}',
+            Some(c @ ('u' | 'x' | 'U')) => {
+                let max = if c == 'U' { 8 } else { 4 };
+                let digits: String = self
+                    .rest()
+                    .chars()
+                    .take_while(|c| c.is_ascii_hexdigit())
+                    .take(max)
+                    .collect();
+                self.bump_n(digits.len());
+                u32::from_str_radix(&digits, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .unwrap_or('\u{fffd}')
+            }
+            Some(c) => c,
+            None => '\\',
+        }
+    }
+}
+
+fn condition_tokens(expression: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut chars = expression.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '(' | ')' => tokens.push(c.to_string()),
+            '!' | '=' | '&' | '|' => {
+                let doubled = if c == '!' { '=' } else { c };
+                if chars.peek() == Some(&doubled) {
+                    chars.next();
+                    tokens.push(format!("{}{}", c, doubled));
+                } else {
+                    tokens.push(c.to_string());
+                }
+            }
+            c => {
+                let mut symbol = c.to_string();
+                while let Some(&next) = chars.peek() {
+                    if !(next.is_alphanumeric() || next == '_') {
+                        break;
+                    }
+                    symbol.push(next);
+                    chars.next();
+                }
+                tokens.push(symbol);
+            }
+        }
+    }
+    tokens
+}
+
+/// Tokenizes `source`, dropping code in `#if` branches that are inactive
+/// for `defined_symbols`
+pub fn tokenize(source: &str, defined_symbols: &[String]) -> Vec<Token> {
+    Lexer::new(source, defined_symbols).tokenize()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(source: &str) -> Vec<String> {
+        tokenize(source, &[])
+            .into_iter()
+            .filter(|t| t.kind != TokenKind::Eof)
+            .map(|t| t.text)
+            .collect()
+    }
+
+    #[test]
+    fn string_literals_keep_braces_and_parentheses() {
+        let tokens = tokenize(r#"Name = "a } b ) c";"#, &[]);
+        assert_eq!(tokens[2].kind, TokenKind::String);
+        assert_eq!(tokens[2].text, "a } b ) c");
+        assert!(tokens[3].is(";"));
+    }
+
+    #[test]
+    fn verbatim_and_raw_strings() {
+        let tokens = tokenize("@\"C:\\dir \"\"quoted\"\"\" \"\"\"raw \" } text\"\"\"", &[]);
+        assert_eq!(tokens[0].text, "C:\\dir \"quoted\"");
+        assert_eq!(tokens[1].text, "raw \" } text");
+    }
+
+    #[test]
+    fn escapes_are_decoded() {
+        let tokens = tokenize(r#""tab\t\u0041" '\n'"#, &[]);
+        assert_eq!(tokens[0].text, "tab\tA");
+        assert_eq!(tokens[1].kind, TokenKind::Char);
+        assert_eq!(tokens[1].text, "\n");
+    }
+
+    #[test]
+    fn comments_are_trivia() {
+        let tokens = tokenize(
+            "// class Hidden { }\n/* enum Gone { A } */\n/// <summary>Doc</summary>\nclass Shown",
+            &[],
+        );
+        assert_eq!(tokens[0].text, "class");
+        let kinds: Vec<TriviaKind> = tokens[0].leading_trivia.iter().map(|t| t.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                TriviaKind::LineComment,
+                TriviaKind::BlockComment,
+                TriviaKind::DocComment
+            ]
+        );
+        assert_eq!(tokens[0].leading_trivia[2].text, " <summary>Doc</summary>");
+    }
+
+    #[test]
+    fn closing_angle_brackets_stay_separate() {
+        assert_eq!(
+            texts("List<List<int>> x"),
+            ["List", "<", "List", "<", "int", ">", ">", "x"]
+        );
+    }
+
+    #[test]
+    fn numbers_with_suffixes_and_exponents() {
+        assert_eq!(
+            texts("0x_FF_u 1.5e-3m 1_000L .5"),
+            ["0x_FF_u", "1.5e-3m", "1_000L", ".5"]
+        );
+    }
+
+    #[test]
+    fn inactive_branches_are_skipped() {
+        let source = "#if DEBUG\nA\n#elif RELEASE && !TRACE\nB\n#else\nC\n#endif\nD";
+        assert_eq!(texts(source), ["C", "D"]);
+        assert_eq!(
+            tokenize(source, &["DEBUG".to_string()])
+                .iter()
+                .map(|t| t.text.as_str())
+                .collect::<Vec<_>>(),
+            ["A", "D", ""]
+        );
+        assert_eq!(
+            Lexer::new(source, &["RELEASE".to_string()])
+                .tokenize()
+                .iter()
+                .map(|t| t.text.as_str())
+                .collect::<Vec<_>>(),
+            ["B", "D", ""]
+        );
+    }
+
+    #[test]
+    fn nested_conditionals_and_defines() {
+        let source = "#define LOCAL\n#if (LOCAL || CI) == true\n#if CI\nA\n#else\nB\n#endif\n#undef LOCAL\n#endif\n#if LOCAL\nC\n#endif";
+        assert_eq!(texts(source), ["B"]);
+    }
+
+    #[test]
+    fn regions_and_pragmas_are_trivia() {
+        let tokens = tokenize(
+            "#region Props\n#pragma warning disable CS0168\nint x;\n#endregion",
+            &[],
+        );
+        assert_eq!(tokens[0].text, "int");
+        assert_eq!(tokens[0].leading_trivia.len(), 2);
+        assert!(tokens[0]
+            .leading_trivia
+            .iter()
+            .all(|t| t.kind == TriviaKind::Directive));
+    }
+
+    #[test]
+    fn disabled_text_may_contain_unbalanced_code() {
+        let tokens = tokenize("#if NEVER\nclass Broken { \"\n#endif\nclass Ok", &[]);
+        assert_eq!(tokens[0].text, "class");
+        assert_eq!(tokens[1].text, "Ok");
+        assert!(tokens[0]
+            .leading_trivia
+            .iter()
+            .any(|t| t.kind == TriviaKind::DisabledText && t.text.contains("Broken")));
+    }
+
+    #[test]
+    fn nullable_directives_set_the_annotation_context() {
+        let tokens = tokenize(
+            "a\n#nullable enable\nb\n#nullable disable warnings\nc\n#nullable disable\nd\n#nullable restore annotations\ne",
+            &[],
+        );
+        let contexts: Vec<Option<bool>> =
+            tokens[..5].iter().map(|t| t.nullable_annotations).collect();
+        assert_eq!(contexts, [None, Some(true), Some(true), Some(false), None]);
+    }
+
+    #[test]
+    fn interpolated_strings_are_marked() {
+        let tokens = tokenize(r#""plain" $"a{1 + 2}b" @$"{x}" $@"{y}" $"""{z}""""#, &[]);
+        let kinds: Vec<TokenKind> = tokens.iter().map(|t| t.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                TokenKind::String,
+                TokenKind::InterpolatedString,
+                TokenKind::InterpolatedString,
+                TokenKind::InterpolatedString,
+                TokenKind::InterpolatedString,
+                TokenKind::Eof
+            ]
+        );
+        assert_eq!(tokens[1].text, "a{1 + 2}b");
+    }
+}
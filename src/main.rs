@@ -1,11 +1,15 @@
 mod config;
+mod lexer;
+mod parser;
 mod processor;
+mod syntax;
+mod xmldoc;
 
 use clap::{Parser, Subcommand};
 use colored::*;
-use config::Config;
+use config::{Config, EnumStyle};
 use notify_debouncer_mini::{new_debouncer, notify::*};
-use processor::{process_single_file, FileProcessor};
+use processor::{process_single_file, FileProcessor, ProcessingStats};
 use std::path::PathBuf;
 use std::sync::mpsc;
 use std::time::Duration;
@@ -32,6 +36,10 @@ enum Commands {
         /// Output directory for TypeScript files
         #[arg(short, long)]
         output: Option<PathBuf>,
+
+        /// Emit numeric enum values instead of display-name strings
+        #[arg(short, long)]
+        numeric: bool,
     },
     /// Generate Zod schemas from C# DTOs
     Schemas {
@@ -91,8 +99,15 @@ async fn main() {
     let config = Config::load().unwrap_or_default();
 
     match cli.command {
-        Commands::Enums { input, output } => {
-            let config = config.clone();
+        Commands::Enums {
+            input,
+            output,
+            numeric,
+        } => {
+            let mut config = config.clone();
+            if numeric {
+                config.enum_style = EnumStyle::Numeric;
+            }
             let input_dir = input
                 .or_else(|| config.input_dir.clone())
                 .expect("Input directory is required");
@@ -126,13 +141,12 @@ async fn main() {
                             "{}",
                             format!("🔄 File changed: {}", modified_path.display()).yellow()
                         );
-                        let mut processor = FileProcessor::new(); // Reset stats for each change
-                        if let Err(e) = process_single_file(
-                            &mut processor,
-                            &modified_path,
-                            &output_dir,
-                            &config,
-                        ) {
+                        // The whole project is reparsed so partial types stay merged,
+                        // and regenerated since importers may depend on the change
+                        processor.stats = ProcessingStats::default();
+                        if let Err(e) =
+                            process_single_file(&mut processor, &input_dir, &output_dir, &config)
+                        {
                             eprintln!("{}: {}", "Error".red(), e);
                         } else {
                             println!(
@@ -197,13 +211,12 @@ async fn main() {
                             "{}",
                             format!("🔄 File changed: {}", modified_path.display()).yellow()
                         );
-                        let mut processor = FileProcessor::new(); // Reset stats for each change
-                        if let Err(e) = process_single_file(
-                            &mut processor,
-                            &modified_path,
-                            &output_dir,
-                            &config,
-                        ) {
+                        // The whole project is reparsed so partial types stay merged,
+                        // and regenerated since importers may depend on the change
+                        processor.stats = ProcessingStats::default();
+                        if let Err(e) =
+                            process_single_file(&mut processor, &input_dir, &output_dir, &config)
+                        {
                             eprintln!("{}: {}", "Error".red(), e);
                         } else {
                             println!("{}", "✨ Zod schemas regenerated successfully!".green());
@@ -0,0 +1,1209 @@
+use crate::lexer::{tokenize, Token, TokenKind, Trivia};
+use crate::syntax::*;
+
+const MODIFIERS: &[&str] = &[
+    "public",
+    "private",
+    "protected",
+    "internal",
+    "static",
+    "readonly",
+    "const",
+    "abstract",
+    "sealed",
+    "virtual",
+    "override",
+    "new",
+    "extern",
+    "unsafe",
+    "volatile",
+    "partial",
+    "async",
+    "required",
+    "file",
+    "ref",
+];
+
+const PARAMETER_MODIFIERS: &[&str] = &["this", "ref", "in", "out", "params", "scoped", "readonly"];
+
+#[derive(Debug, thiserror::Error)]
+#[error("line {line}:{column}: {message}")]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+pub fn parse(source: &str, defined_symbols: &[String]) -> Result<CompilationUnit, ParseError> {
+    Parser::new(tokenize(source, defined_symbols)).parse_compilation_unit()
+}
+
+pub struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    pub fn new(tokens: Vec<Token>) -> Self {
+        Self { tokens, pos: 0 }
+    }
+
+    fn peek(&self) -> &Token {
+        self.peek_at(0)
+    }
+
+    fn peek_at(&self, offset: usize) -> &Token {
+        let index = (self.pos + offset).min(self.tokens.len() - 1);
+        &self.tokens[index]
+    }
+
+    fn at_eof(&self) -> bool {
+        self.peek().kind == TokenKind::Eof
+    }
+
+    fn advance(&mut self) -> Token {
+        let token = self.peek().clone();
+        if !self.at_eof() {
+            self.pos += 1;
+        }
+        token
+    }
+
+    fn eat(&mut self, text: &str) -> bool {
+        if self.peek().is(text) {
+            self.advance();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, text: &str) -> Result<Token, ParseError> {
+        if self.peek().is(text) {
+            Ok(self.advance())
+        } else {
+            Err(self.error(&format!("expected `{}`", text)))
+        }
+    }
+
+    fn expect_identifier(&mut self) -> Result<String, ParseError> {
+        if self.peek().is_identifier() {
+            Ok(self.advance().text)
+        } else {
+            Err(self.error("expected identifier"))
+        }
+    }
+
+    fn error(&self, message: &str) -> ParseError {
+        let token = self.peek();
+        let found = if token.kind == TokenKind::Eof {
+            "end of file".to_string()
+        } else {
+            format!("`{}`", token.text)
+        };
+        ParseError {
+            line: token.line,
+            column: token.column,
+            message: format!("{}, found {}", message, found),
+        }
+    }
+
+    fn leading_trivia(&self) -> Vec<Trivia> {
+        self.peek().leading_trivia.clone()
+    }
+
+    pub fn parse_compilation_unit(&mut self) -> Result<CompilationUnit, ParseError> {
+        let (usings, members) = self.parse_namespace_body(false)?;
+        Ok(CompilationUnit { usings, members })
+    }
+
+    fn parse_namespace_body(
+        &mut self,
+        braced: bool,
+    ) -> Result<(Vec<UsingDirective>, Vec<NamespaceMember>), ParseError> {
+        let mut usings = Vec::new();
+        let mut members = Vec::new();
+
+        loop {
+            if self.at_eof() || (braced && self.peek().is("}")) {
+                break;
+            }
+
+            if self.eat(";") {
+                continue;
+            }
+
+            if self.at_using_directive() {
+                usings.push(self.parse_using_directive()?);
+                continue;
+            }
+
+            if self.peek().is("extern") && self.peek_at(1).is("alias") {
+                self.skip_member();
+                continue;
+            }
+
+            if self.peek().is("namespace") {
+                let namespace = self.parse_namespace()?;
+                let file_scoped = namespace.file_scoped;
+                members.push(NamespaceMember::Namespace(namespace));
+                if file_scoped {
+                    break;
+                }
+                continue;
+            }
+
+            // Assembly and module level attributes
+            if self.peek().is("[") && self.peek_at(2).is(":") {
+                self.parse_attributes()?;
+                continue;
+            }
+
+            let trivia = self.leading_trivia();
+            let start = self.pos;
+            let attributes = self.parse_attributes()?;
+            let modifiers = self.parse_modifiers();
+            if self.at_type_declaration() {
+                if let Some(decl) = self.parse_type_declaration(attributes, modifiers, trivia)? {
+                    members.push(NamespaceMember::Type(decl));
+                }
+            } else {
+                // Top-level statements and anything else we do not model
+                self.pos = start;
+                self.skip_member();
+            }
+        }
+
+        Ok((usings, members))
+    }
+
+    fn at_using_directive(&self) -> bool {
+        let offset = if self.peek().is("global") { 1 } else { 0 };
+        self.peek_at(offset).is("using")
+            && !self.peek_at(offset + 1).is("(")
+            && !self.peek_at(offset + 1).is("var")
+    }
+
+    fn parse_using_directive(&mut self) -> Result<UsingDirective, ParseError> {
+        let is_global = self.eat("global");
+        self.expect("using")?;
+        // `using static` brings a type's nested types into scope much like a
+        // namespace, which is all type references need
+        self.eat("static");
+        let alias = if self.peek().is_identifier() && self.peek_at(1).is("=") {
+            let alias = self.advance().text;
+            self.advance();
+            Some(alias)
+        } else {
+            None
+        };
+        let target = self.parse_type()?;
+        self.expect(";")?;
+        Ok(UsingDirective {
+            is_global,
+            alias,
+            target,
+        })
+    }
+
+    fn parse_namespace(&mut self) -> Result<NamespaceDecl, ParseError> {
+        self.expect("namespace")?;
+        let name = self.parse_qualified_name()?;
+
+        let file_scoped = self.eat(";");
+        if !file_scoped {
+            self.expect("{")?;
+        }
+        let (usings, members) = self.parse_namespace_body(!file_scoped)?;
+        if !file_scoped {
+            self.expect("}")?;
+            self.eat(";");
+        }
+
+        Ok(NamespaceDecl {
+            name,
+            file_scoped,
+            usings,
+            members,
+        })
+    }
+
+    fn parse_qualified_name(&mut self) -> Result<String, ParseError> {
+        let mut name = self.expect_identifier()?;
+        while self.peek().is(".") || self.peek().is("::") {
+            name.push_str(&self.advance().text);
+            name.push_str(&self.expect_identifier()?);
+        }
+        Ok(name)
+    }
+
+    fn parse_attributes(&mut self) -> Result<Vec<Attribute>, ParseError> {
+        let mut attributes = Vec::new();
+        while self.eat("[") {
+            // Attribute target such as `assembly:` or `property:`
+            if self.peek().is_identifier() && self.peek_at(1).is(":") {
+                self.advance();
+                self.advance();
+            }
+            loop {
+                attributes.push(self.parse_attribute()?);
+                if !self.eat(",") {
+                    break;
+                }
+                if self.peek().is("]") {
+                    break;
+                }
+            }
+            self.expect("]")?;
+        }
+        Ok(attributes)
+    }
+
+    fn parse_attribute(&mut self) -> Result<Attribute, ParseError> {
+        let name = self.parse_qualified_name()?;
+        let mut arguments = Vec::new();
+        if self.eat("(") {
+            while !self.peek().is(")") {
+                let named = self.peek().is_identifier()
+                    && (self.peek_at(1).is("=") || self.peek_at(1).is(":"));
+                let name = if named {
+                    let name = self.advance().text;
+                    self.advance();
+                    Some(name)
+                } else {
+                    None
+                };
+                let value = self.parse_expression(&[",", ")"])?;
+                arguments.push(AttributeArgument { name, value });
+                if !self.eat(",") {
+                    break;
+                }
+            }
+            self.expect(")")?;
+        }
+        Ok(Attribute { name, arguments })
+    }
+
+    fn parse_modifiers(&mut self) -> Vec<String> {
+        let mut modifiers = Vec::new();
+        while MODIFIERS.iter().any(|m| self.peek().is(m)) {
+            modifiers.push(self.advance().text);
+        }
+        modifiers
+    }
+
+    fn at_type_declaration(&self) -> bool {
+        let token = self.peek();
+        token.is("class")
+            || token.is("struct")
+            || token.is("interface")
+            || token.is("enum")
+            || token.is("delegate")
+            || (token.is("record") && self.peek_at(1).is_identifier())
+    }
+
+    fn parse_type_declaration(
+        &mut self,
+        attributes: Vec<Attribute>,
+        modifiers: Vec<String>,
+        trivia: Vec<Trivia>,
+    ) -> Result<Option<TypeDecl>, ParseError> {
+        let keyword = self.advance().text;
+        let kind = match keyword.as_str() {
+            "class" => TypeKind::Class,
+            "struct" => TypeKind::Struct,
+            "interface" => TypeKind::Interface,
+            "enum" => TypeKind::Enum,
+            "record" => {
+                if self.eat("struct") {
+                    TypeKind::RecordStruct
+                } else {
+                    self.eat("class");
+                    TypeKind::Record
+                }
+            }
+            _ => {
+                // Delegates carry no data we can generate
+                self.skip_member();
+                return Ok(None);
+            }
+        };
+
+        let name = self.expect_identifier()?;
+        let type_parameters = self.parse_type_parameters()?;
+        let parameters = if self.peek().is("(") {
+            Some(self.parse_parameter_list()?)
+        } else {
+            None
+        };
+
+        let mut base_types = Vec::new();
+        if self.eat(":") {
+            loop {
+                base_types.push(self.parse_type()?);
+                // Base constructor arguments of records and primary constructors
+                if self.peek().is("(") {
+                    self.skip_balanced();
+                }
+                if !self.eat(",") {
+                    break;
+                }
+            }
+        }
+
+        while self.peek().is("where") {
+            while !self.at_eof()
+                && !self.peek().is("{")
+                && !self.peek().is(";")
+                && !self.peek().is("=>")
+            {
+                if self.peek().is("(") {
+                    self.skip_balanced();
+                } else {
+                    self.advance();
+                }
+            }
+        }
+
+        let mut members = Vec::new();
+        if self.eat("{") {
+            members = if kind == TypeKind::Enum {
+                self.parse_enum_members()?
+            } else {
+                self.parse_members(&name)?
+            };
+            self.expect("}")?;
+            self.eat(";");
+        } else {
+            self.expect(";")?;
+        }
+
+        Ok(Some(TypeDecl {
+            kind,
+            full_name: name.clone(),
+            name,
+            type_parameters,
+            modifiers,
+            attributes,
+            trivia,
+            parameters,
+            base_types,
+            members,
+        }))
+    }
+
+    fn parse_type_parameters(&mut self) -> Result<Vec<String>, ParseError> {
+        let mut parameters = Vec::new();
+        if self.eat("<") {
+            loop {
+                self.parse_attributes()?;
+                if self.peek().is("in") || self.peek().is("out") {
+                    self.advance();
+                }
+                parameters.push(self.expect_identifier()?);
+                if !self.eat(",") {
+                    break;
+                }
+            }
+            self.expect(">")?;
+        }
+        Ok(parameters)
+    }
+
+    fn parse_parameter_list(&mut self) -> Result<Vec<Parameter>, ParseError> {
+        let mut parameters = Vec::new();
+        self.expect("(")?;
+        while !self.peek().is(")") {
+            let trivia = self.leading_trivia();
+            let attributes = self.parse_attributes()?;
+            // `ref`, `in`, `params` and the like don't change the serialized shape
+            while PARAMETER_MODIFIERS.iter().any(|m| self.peek().is(m)) {
+                self.advance();
+            }
+            let nullable_annotations = self.peek().nullable_annotations;
+            let type_name = self.parse_type()?;
+            let name = self.expect_identifier()?;
+            let default_value = if self.eat("=") {
+                Some(self.parse_expression(&[",", ")"])?)
+            } else {
+                None
+            };
+            parameters.push(Parameter {
+                name,
+                type_name,
+                attributes,
+                trivia,
+                default_value,
+                nullable_annotations,
+            });
+            if !self.eat(",") {
+                break;
+            }
+        }
+        self.expect(")")?;
+        Ok(parameters)
+    }
+
+    fn parse_enum_members(&mut self) -> Result<Vec<MemberDecl>, ParseError> {
+        let mut members = Vec::new();
+        while !self.at_eof() && !self.peek().is("}") {
+            let trivia = self.leading_trivia();
+            let attributes = self.parse_attributes()?;
+            let name = self.expect_identifier()?;
+            let value = if self.eat("=") {
+                Some(self.parse_expression(&[",", "}"])?)
+            } else {
+                None
+            };
+            members.push(MemberDecl::EnumMember(EnumMemberDecl {
+                name,
+                attributes,
+                trivia,
+                value,
+            }));
+            if !self.eat(",") {
+                break;
+            }
+        }
+        Ok(members)
+    }
+
+    fn parse_members(&mut self, type_name: &str) -> Result<Vec<MemberDecl>, ParseError> {
+        let mut members = Vec::new();
+        while !self.at_eof() && !self.peek().is("}") {
+            if self.eat(";") {
+                continue;
+            }
+
+            let trivia = self.leading_trivia();
+            let start = self.pos;
+            let attributes = self.parse_attributes()?;
+            let modifiers = self.parse_modifiers();
+
+            if self.at_type_declaration() {
+                if let Some(decl) = self.parse_type_declaration(attributes, modifiers, trivia)? {
+                    members.push(MemberDecl::Type(decl));
+                }
+                continue;
+            }
+
+            // Members we cannot make sense of are skipped rather than failing the file
+            match self.parse_member(type_name, attributes, modifiers, trivia) {
+                Ok(Some(parsed)) => members.extend(parsed),
+                Ok(None) => {}
+                Err(_) => {
+                    self.pos = start;
+                    self.skip_member();
+                }
+            }
+        }
+        Ok(members)
+    }
+
+    fn parse_member(
+        &mut self,
+        type_name: &str,
+        attributes: Vec<Attribute>,
+        modifiers: Vec<String>,
+        trivia: Vec<Trivia>,
+    ) -> Result<Option<Vec<MemberDecl>>, ParseError> {
+        let token = self.peek();
+        let is_constructor = token.is(type_name) && self.peek_at(1).is("(");
+        let is_other = ["event", "~", "implicit", "explicit", "operator"]
+            .iter()
+            .any(|t| token.is(t));
+        if is_constructor || is_other {
+            self.skip_member();
+            return Ok(None);
+        }
+
+        let nullable_annotations = self.peek().nullable_annotations;
+        let type_name = self.parse_type()?;
+        if !self.peek().is_identifier() || self.peek().is("operator") || self.peek().is("this") {
+            self.skip_member();
+            return Ok(None);
+        }
+
+        let name = self.advance().text;
+        let next = self.peek();
+
+        if next.is("{") {
+            let accessors = self.parse_accessors()?;
+            let initializer = if self.eat("=") {
+                let value = self.parse_expression(&[";"])?;
+                self.expect(";")?;
+                Some(value)
+            } else {
+                None
+            };
+            return Ok(Some(vec![MemberDecl::Property(PropertyDecl {
+                name,
+                type_name,
+                modifiers,
+                attributes,
+                trivia,
+                accessors,
+                initializer,
+                nullable_annotations,
+            })]));
+        }
+
+        if next.is("=>") {
+            self.skip_member();
+            return Ok(Some(vec![MemberDecl::Property(PropertyDecl {
+                name,
+                type_name,
+                modifiers,
+                attributes,
+                trivia,
+                accessors: vec!["get".to_string()],
+                initializer: None,
+                nullable_annotations,
+            })]));
+        }
+
+        if next.is("=") || next.is(",") || next.is(";") {
+            let mut fields = Vec::new();
+            let mut name = name;
+            loop {
+                let initializer = if self.eat("=") {
+                    Some(self.parse_expression(&[",", ";"])?)
+                } else {
+                    None
+                };
+                fields.push(MemberDecl::Field(FieldDecl {
+                    name,
+                    type_name: type_name.clone(),
+                    modifiers: modifiers.clone(),
+                    attributes: attributes.clone(),
+                    trivia: trivia.clone(),
+                    initializer,
+                }));
+                if !self.eat(",") {
+                    break;
+                }
+                name = self.expect_identifier()?;
+            }
+            self.expect(";")?;
+            return Ok(Some(fields));
+        }
+
+        // Methods, explicit interface implementations and the like
+        self.skip_member();
+        Ok(None)
+    }
+
+    fn parse_accessors(&mut self) -> Result<Vec<String>, ParseError> {
+        let mut accessors = Vec::new();
+        self.expect("{")?;
+        while !self.at_eof() && !self.peek().is("}") {
+            self.parse_attributes()?;
+            self.parse_modifiers();
+            accessors.push(self.expect_identifier()?);
+            if self.peek().is("{") {
+                self.skip_balanced();
+            } else if self.eat("=>") {
+                self.parse_expression(&[";"])?;
+                self.expect(";")?;
+            } else {
+                self.expect(";")?;
+            }
+        }
+        self.expect("}")?;
+        Ok(accessors)
+    }
+
+    /// Parses a type reference: qualified and generic names, tuples, and
+    /// nullable, pointer and array suffixes
+    fn parse_type(&mut self) -> Result<TypeSyntax, ParseError> {
+        let mut syntax = if self.eat("(") {
+            let mut elements = Vec::new();
+            loop {
+                let element = self.parse_type()?;
+                let name = if self.peek().is_identifier() {
+                    Some(self.advance().text)
+                } else {
+                    None
+                };
+                elements.push(TupleElement {
+                    type_name: element,
+                    name,
+                });
+                if !self.eat(",") {
+                    break;
+                }
+            }
+            self.expect(")")?;
+            TypeSyntax::Tuple(elements)
+        } else {
+            if self.peek().is("global") && self.peek_at(1).is("::") {
+                self.advance();
+                self.advance();
+            }
+            let mut qualifier = Vec::new();
+            let mut name = self.expect_identifier()?;
+            let mut arguments = self.parse_type_arguments()?;
+            while self.peek().is(".") || self.peek().is("::") {
+                self.advance();
+                qualifier.push(name);
+                name = self.expect_identifier()?;
+                arguments = self.parse_type_arguments()?;
+            }
+            TypeSyntax::Named {
+                qualifier,
+                name,
+                arguments,
+            }
+        };
+
+        loop {
+            if self.eat("?") {
+                syntax = TypeSyntax::Nullable(Box::new(syntax));
+            } else if self.eat("*") {
+                syntax = TypeSyntax::Pointer(Box::new(syntax));
+            } else if self.peek().is("[") && (self.peek_at(1).is("]") || self.peek_at(1).is(",")) {
+                self.advance();
+                let mut rank = 1;
+                while self.eat(",") {
+                    rank += 1;
+                }
+                self.expect("]")?;
+                syntax = TypeSyntax::Array(Box::new(syntax), rank);
+            } else {
+                break;
+            }
+        }
+
+        Ok(syntax)
+    }
+
+    fn parse_type_arguments(&mut self) -> Result<Vec<TypeSyntax>, ParseError> {
+        let mut arguments = Vec::new();
+        if self.eat("<") {
+            loop {
+                arguments.push(self.parse_type()?);
+                if !self.eat(",") {
+                    break;
+                }
+            }
+            self.expect(">")?;
+        }
+        Ok(arguments)
+    }
+
+    /// Reads an expression up to (not including) one of `stops` at nesting depth zero
+    fn parse_expression(&mut self, stops: &[&str]) -> Result<Expr, ParseError> {
+        let start = self.pos;
+        let mut depth = 0usize;
+        while !self.at_eof() {
+            let token = self.peek();
+            if depth == 0 && stops.iter().any(|s| token.is(s)) {
+                break;
+            }
+            if token.is("(") || token.is("[") || token.is("{") {
+                depth += 1;
+            } else if token.is(")") || token.is("]") || token.is("}") {
+                if depth == 0 {
+                    break;
+                }
+                depth -= 1;
+            }
+            self.advance();
+        }
+
+        let tokens = &self.tokens[start..self.pos];
+        if tokens.is_empty() {
+            return Err(self.error("expected expression"));
+        }
+        Ok(expression_from_tokens(tokens))
+    }
+
+    /// Consumes a parenthesized, bracketed or braced group including its delimiters
+    fn skip_balanced(&mut self) {
+        let mut depth = 0usize;
+        while !self.at_eof() {
+            let token = self.advance();
+            if token.is("(") || token.is("[") || token.is("{") {
+                depth += 1;
+            } else if token.is(")") || token.is("]") || token.is("}") {
+                depth = depth.saturating_sub(1);
+            }
+            if depth == 0 {
+                break;
+            }
+        }
+    }
+
+    /// Skips a member or statement up to its terminating `;` or closing `}`
+    fn skip_member(&mut self) {
+        while !self.at_eof() {
+            let token = self.peek();
+            if token.is("}") {
+                break;
+            }
+            if token.is(";") {
+                self.advance();
+                break;
+            }
+            if token.is("{") {
+                self.skip_balanced();
+                self.eat(";");
+                break;
+            }
+            if token.is("(") || token.is("[") {
+                self.skip_balanced();
+            } else {
+                self.advance();
+            }
+        }
+    }
+}
+
+fn expression_from_tokens(tokens: &[Token]) -> Expr {
+    let mut parser = ExpressionParser { tokens, pos: 0 };
+    match parser.parse_binary(0) {
+        Some(expr) if parser.pos == tokens.len() => expr,
+        _ => Expr::Raw(source_text(tokens)),
+    }
+}
+
+// Binary operators from loosest to tightest binding
+const BINARY_PRECEDENCE: &[&[&str]] = &[
+    &["??"],
+    &["||"],
+    &["&&"],
+    &["|"],
+    &["^"],
+    &["&"],
+    &["==", "!="],
+    &["<", ">", "<=", ">="],
+    &["<<", ">>"],
+    &["+", "-"],
+    &["*", "/", "%"],
+];
+
+/// Precedence-climbing parser over the tokens of a single constant expression
+struct ExpressionParser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+}
+
+impl ExpressionParser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn adjacent(&self, offset: usize, text: &str) -> bool {
+        match (
+            self.tokens.get(self.pos + offset - 1),
+            self.tokens.get(self.pos + offset),
+        ) {
+            (Some(previous), Some(token)) => token.is(text) && previous.end == token.start,
+            _ => false,
+        }
+    }
+
+    /// The lexer never joins `>`, so `>>` and `>=` are reassembled here
+    fn peek_operator(&self) -> Option<(String, usize)> {
+        let token = self.peek()?;
+        if token.kind != TokenKind::Punct {
+            return None;
+        }
+        if token.is(">") && self.adjacent(1, ">") {
+            return Some((">>".to_string(), 2));
+        }
+        if token.is(">") && self.adjacent(1, "=") {
+            return Some((">=".to_string(), 2));
+        }
+        Some((token.text.clone(), 1))
+    }
+
+    fn parse_binary(&mut self, level: usize) -> Option<Expr> {
+        if level == BINARY_PRECEDENCE.len() {
+            return self.parse_unary();
+        }
+        let mut left = self.parse_binary(level + 1)?;
+        while let Some((op, width)) = self.peek_operator() {
+            if !BINARY_PRECEDENCE[level].contains(&op.as_str()) {
+                break;
+            }
+            self.pos += width;
+            let right = self.parse_binary(level + 1)?;
+            left = Expr::Binary(Box::new(left), op, Box::new(right));
+        }
+        Some(left)
+    }
+
+    fn parse_unary(&mut self) -> Option<Expr> {
+        let token = self.peek()?;
+        if ["-", "+", "~", "!"].iter().any(|op| token.is(op)) {
+            let op = token.text.clone();
+            self.pos += 1;
+            let operand = self.parse_unary()?;
+            return Some(match (op.as_str(), operand) {
+                ("-", Expr::Number(n)) => Expr::Number(format!("-{}", n)),
+                (_, operand) => Expr::Unary(op, Box::new(operand)),
+            });
+        }
+        if let Some(cast) = self.try_parse_cast() {
+            return Some(cast);
+        }
+        self.parse_primary()
+    }
+
+    /// `(int)Other.Value`: a parenthesized name followed by an operand
+    fn try_parse_cast(&mut self) -> Option<Expr> {
+        let [open, name, close, next, ..] = self.tokens.get(self.pos..)? else {
+            return None;
+        };
+        let operand_follows = next.kind != TokenKind::Punct || next.is("(");
+        if !open.is("(") || !name.is_identifier() || !close.is(")") || !operand_follows {
+            return None;
+        }
+        let type_name = TypeSyntax::Named {
+            qualifier: Vec::new(),
+            name: name.text.clone(),
+            arguments: Vec::new(),
+        };
+        let start = self.pos;
+        self.pos += 3;
+        match self.parse_unary() {
+            Some(operand) => Some(Expr::Cast(type_name, Box::new(operand))),
+            None => {
+                self.pos = start;
+                None
+            }
+        }
+    }
+
+    fn parse_primary(&mut self) -> Option<Expr> {
+        let token = self.peek()?.clone();
+        self.pos += 1;
+        match token.kind {
+            TokenKind::String => Some(Expr::String(token.text)),
+            // Only known once the holes are evaluated at run time
+            TokenKind::InterpolatedString => Some(Expr::Raw(source_text(&[token]))),
+            TokenKind::Char => Some(Expr::Char(token.text)),
+            TokenKind::Number => Some(Expr::Number(token.text)),
+            TokenKind::Identifier => match token.text.as_str() {
+                "true" => Some(Expr::Bool(true)),
+                "false" => Some(Expr::Bool(false)),
+                "null" => Some(Expr::Null),
+                "new" => self.parse_new(),
+                _ => {
+                    let mut name = token.text;
+                    while self.peek().is_some_and(|t| t.is("."))
+                        && self
+                            .tokens
+                            .get(self.pos + 1)
+                            .is_some_and(|t| t.is_identifier())
+                    {
+                        name.push('.');
+                        name.push_str(&self.tokens[self.pos + 1].text);
+                        self.pos += 2;
+                    }
+                    Some(Expr::Name(name))
+                }
+            },
+            _ if token.is("(") => {
+                let inner = self.parse_binary(0)?;
+                if !self.peek()?.is(")") {
+                    return None;
+                }
+                self.pos += 1;
+                Some(inner)
+            }
+            _ if token.is("[") => self.parse_list("]").map(Expr::Collection),
+            _ => None,
+        }
+    }
+
+    /// `new()`, `new T(...)` and array creation with an initializer;
+    /// object initializers are not modeled
+    fn parse_new(&mut self) -> Option<Expr> {
+        if self.peek()?.is("[") && self.tokens.get(self.pos + 1)?.is("]") {
+            self.pos += 2;
+            return self.parse_array_initializer();
+        }
+
+        let type_name = if self.peek()?.is("(") {
+            None
+        } else {
+            let mut parser = Parser::new(self.tokens[self.pos..].to_vec());
+            parser.tokens.push(end_of_input(self.tokens.last()?));
+            let type_name = parser.parse_type().ok()?;
+            self.pos += parser.pos;
+            Some(type_name)
+        };
+        if let Some(TypeSyntax::Array(..)) = type_name {
+            return self.parse_array_initializer();
+        }
+
+        if !self.peek()?.is("(") {
+            return None;
+        }
+        self.pos += 1;
+        let arguments = self.parse_list(")")?;
+        if self.peek().is_some_and(|t| t.is("{")) {
+            return None;
+        }
+        Some(Expr::New(type_name, arguments))
+    }
+
+    fn parse_array_initializer(&mut self) -> Option<Expr> {
+        if !self.peek()?.is("{") {
+            return None;
+        }
+        self.pos += 1;
+        self.parse_list("}").map(Expr::Collection)
+    }
+
+    /// Comma-separated expressions up to and including `close`
+    fn parse_list(&mut self, close: &str) -> Option<Vec<Expr>> {
+        let mut elements = Vec::new();
+        loop {
+            if self.peek()?.is(close) {
+                self.pos += 1;
+                return Some(elements);
+            }
+            elements.push(self.parse_binary(0)?);
+            if self.peek()?.is(",") {
+                self.pos += 1;
+            } else if !self.peek()?.is(close) {
+                return None;
+            }
+        }
+    }
+}
+
+fn end_of_input(last: &Token) -> Token {
+    Token {
+        kind: TokenKind::Eof,
+        text: String::new(),
+        start: last.end,
+        end: last.end,
+        line: last.line,
+        column: last.column,
+        leading_trivia: Vec::new(),
+        nullable_annotations: last.nullable_annotations,
+    }
+}
+
+/// Reassembles tokens into readable source, inserting spaces where the
+/// original had any
+fn source_text(tokens: &[Token]) -> String {
+    let mut text = String::new();
+    for (i, token) in tokens.iter().enumerate() {
+        if i > 0 && tokens[i - 1].end < token.start {
+            text.push(' ');
+        }
+        match token.kind {
+            TokenKind::String => text.push_str(&format!("{:?}", token.text)),
+            TokenKind::InterpolatedString => text.push_str(&format!("${:?}", token.text)),
+            TokenKind::Char => text.push_str(&format!("'{}'", token.text)),
+            _ => text.push_str(&token.text),
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_ok(source: &str) -> CompilationUnit {
+        parse(source, &[]).expect("source should parse")
+    }
+
+    fn properties(decl: &TypeDecl) -> Vec<&PropertyDecl> {
+        decl.members
+            .iter()
+            .filter_map(|member| match member {
+                MemberDecl::Property(property) => Some(property),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn method_bodies_with_nested_braces_are_skipped() {
+        let unit = parse_ok(
+            r#"
+            namespace App.Dtos;
+
+            public class StudentDto
+            {
+                public string Name { get; set; }
+
+                public void Validate()
+                {
+                    if (Name == null) { throw new Exception("}"); }
+                    foreach (var c in Name) { { } }
+                }
+
+                public int Age { get; init; }
+            }
+
+            public enum Status { Active, Inactive }
+            "#,
+        );
+        let types = unit.types();
+        assert_eq!(types.len(), 2);
+        let names: Vec<&str> = properties(types[0])
+            .iter()
+            .map(|p| p.name.as_str())
+            .collect();
+        assert_eq!(names, ["Name", "Age"]);
+        assert_eq!(types[1].kind, TypeKind::Enum);
+        assert_eq!(unit.namespace().as_deref(), Some("App.Dtos"));
+    }
+
+    #[test]
+    fn attribute_arguments_may_contain_commas() {
+        let unit = parse_ok(
+            r#"
+            public class Dto
+            {
+                [StringLength(50, MinimumLength = 2, ErrorMessage = "Between 2, and 50 (chars)")]
+                [RegularExpression(@"^[a-z]{1,3}$")]
+                public string Code { get; set; }
+            }
+            "#,
+        );
+        let property = properties(unit.types()[0])[0];
+        let length = &property.attributes[0];
+        assert!(length.is("StringLength"));
+        assert_eq!(length.positional(0), Some(&Expr::Number("50".to_string())));
+        assert_eq!(
+            length.named("ErrorMessage").map(Expr::as_text).as_deref(),
+            Some("Between 2, and 50 (chars)")
+        );
+        assert_eq!(
+            property.attributes[1]
+                .positional(0)
+                .map(Expr::as_text)
+                .as_deref(),
+            Some("^[a-z]{1,3}$")
+        );
+    }
+
+    #[test]
+    fn defaults_with_parentheses_and_strings() {
+        let unit = parse_ok(
+            r#"
+            public record Filter(string Query = ")", int Page = (1 + 2) * 3, string Sort = "name}");
+            "#,
+        );
+        let parameters = unit.types()[0].parameters.as_ref().unwrap();
+        let defaults: Vec<String> = parameters
+            .iter()
+            .map(|p| p.default_value.as_ref().unwrap().as_text())
+            .collect();
+        assert_eq!(defaults, [")", "(1 + 2) * 3", "name}"]);
+    }
+
+    #[test]
+    fn commented_out_members_are_ignored() {
+        let unit = parse_ok(
+            r#"
+            public class Dto
+            {
+                // public string Old { get; set; }
+                /* public class Hidden { public int X { get; set; } } */
+                public string Current { get; set; }
+            }
+            "#,
+        );
+        let types = unit.types();
+        assert_eq!(types.len(), 1);
+        let names: Vec<&str> = properties(types[0])
+            .iter()
+            .map(|p| p.name.as_str())
+            .collect();
+        assert_eq!(names, ["Current"]);
+    }
+
+    #[test]
+    fn reports_the_position_of_errors() {
+        let error = parse("public class {", &[]).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 14));
+    }
+
+    #[test]
+    fn nested_generic_types() {
+        let unit = parse_ok(
+            "public class Dto { public Dictionary<string, List<int?>>? Map { get; set; } public (int Id, string[] Tags) Pair { get; set; } }",
+        );
+        let types = properties(unit.types()[0]);
+        assert_eq!(
+            types[0].type_name.to_string(),
+            "Dictionary<string, List<int?>>?"
+        );
+        assert_eq!(types[1].type_name.to_string(), "(int Id, string[] Tags)");
+    }
+
+    #[test]
+    fn qualified_names_and_array_ranks() {
+        let unit = parse_ok(
+            "public class Dto { public global::System.Collections.Generic.List<string> Names { get; set; } public int[,][] Grid { get; set; } }",
+        );
+        let types = properties(unit.types()[0]);
+        match &types[0].type_name {
+            TypeSyntax::Named {
+                qualifier,
+                name,
+                arguments,
+            } => {
+                assert_eq!(qualifier, &["System", "Collections", "Generic"]);
+                assert_eq!(name, "List");
+                assert_eq!(arguments.len(), 1);
+            }
+            other => panic!("expected a name, got {:?}", other),
+        }
+        match &types[1].type_name {
+            TypeSyntax::Array(element, 1) => {
+                assert!(matches!(element.as_ref(), TypeSyntax::Array(_, 2)))
+            }
+            other => panic!("expected a jagged array, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn enum_values_are_expressions() {
+        let unit =
+            parse_ok("public enum Flags : byte { None = 0, A = 1 << 0, B = 1 << 1, All = A | B }");
+        let decl = unit.types()[0];
+        assert_eq!(decl.base_types[0].to_string(), "byte");
+        let values: Vec<Option<i128>> = decl
+            .members
+            .iter()
+            .map(|member| match member {
+                MemberDecl::EnumMember(value) => {
+                    let known = |name: &str| match name {
+                        "A" => Some(1),
+                        "B" => Some(2),
+                        _ => None,
+                    };
+                    value.value.as_ref().and_then(|v| v.evaluate(&known))
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(values, [Some(0), Some(1), Some(2), Some(3)]);
+    }
+
+    #[test]
+    fn using_aliases_and_nested_namespaces() {
+        let unit = parse_ok(
+            r#"
+            global using Id = System.Guid;
+            using static System.Math;
+            namespace Outer { namespace Inner { public class Dto { public Id Key { get; set; } } } }
+            "#,
+        );
+        assert_eq!(unit.usings.len(), 2);
+        assert!(unit.usings[0].is_global);
+        assert_eq!(unit.usings[0].alias.as_deref(), Some("Id"));
+        assert_eq!(unit.namespace().as_deref(), Some("Outer.Inner"));
+    }
+}
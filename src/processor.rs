@@ -1,33 +1,25 @@
-use crate::config::Config;
+use crate::config::{
+    Config, EnumStyle, ImportConfig, OutputLayout, Precision, TypeMapping, WrapperStructStyle,
+};
+use crate::parser;
+use crate::syntax::{
+    self, Attribute, CompilationUnit, Expr, MemberDecl, Parameter, PropertyDecl, TypeDecl,
+    TypeKind, TypeSyntax,
+};
+use crate::xmldoc::XmlDoc;
 use chrono::Local;
 use colored::*;
-use lazy_static::lazy_static;
-use regex::Regex;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 
-lazy_static! {
-    static ref ENUM_REGEX: Regex =
-        Regex::new(r"public\s+enum\s+(?P<name>\w+)\s*\{(?P<body>[^}]+)\}").unwrap();
-    static ref DISPLAY_ATTR_REGEX: Regex =
-        Regex::new(r#"\[Display\(Name\s*=\s*"([^"]+)"\)\]"#).unwrap();
-    static ref DTO_REGEX: Regex =
-        Regex::new(r"public\s+record\s+(?P<name>\w+)\s*\((?P<props>[^)]+)\)").unwrap();
-    static ref PROPERTY_REGEX: Regex =
-        Regex::new(r"(?m)(?P<type>[a-zA-Z0-9_<>?\[\]\.]+)\s+(?P<name>[a-zA-Z0-9_]+)(?:\s*,|\s*$)")
-            .unwrap();
-    static ref VALIDATION_REGEX: Regex = Regex::new(r"\[(?P<attr>[^\]]+)\]").unwrap();
-    static ref DOC_COMMENT_REGEX: Regex = Regex::new(r"///\s*<(?:summary|remarks|example)>(.*?)</(?:summary|remarks|example)>").unwrap();
-    static ref PROP_DOC_REGEX: Regex = 
-        Regex::new(r#"(?m)^\s*///\s*<(?:summary|remarks|example)>(.*?)</(?:summary|remarks|example)>\s*(?:[^\n]*\n)*\s*(?P<type>[a-zA-Z0-9_<>?\[\]\.]+)\s+(?P<name>[a-zA-Z0-9_]+)"#).unwrap();
-}
-
 #[derive(Debug, Default)]
 pub struct ProcessingStats {
     pub files_processed: usize,
     pub enums_generated: usize,
+    pub constants_generated: usize,
     pub schemas_generated: usize,
+    pub interfaces_generated: usize,
     pub files_skipped: usize,
 }
 
@@ -42,10 +34,18 @@ impl ProcessingStats {
             "├─ Enums generated: {}",
             self.enums_generated.to_string().green()
         );
+        println!(
+            "├─ Constant classes generated: {}",
+            self.constants_generated.to_string().green()
+        );
         println!(
             "├─ Schemas generated: {}",
             self.schemas_generated.to_string().green()
         );
+        println!(
+            "├─ Interfaces generated: {}",
+            self.interfaces_generated.to_string().green()
+        );
         println!(
             "└─ Files skipped: {}",
             self.files_skipped.to_string().yellow()
@@ -60,54 +60,213 @@ pub struct FileProcessor {
     pub stats: ProcessingStats,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 enum CSharpType {
     String,
-    Int,
+    Char,
+    /// Integral type by bit width and signedness
+    Integer {
+        bits: u32,
+        signed: bool,
+    },
+    /// 64-bit integer represented as a `bigint`
+    BigInteger {
+        signed: bool,
+        coerce: bool,
+    },
+    /// Integer or decimal carried as a string of digits
+    NumericString {
+        integral: bool,
+        signed: bool,
+    },
     Double,
     Decimal,
     Bool,
     DateTime,
+    DateTimeOffset,
+    DateOnly,
+    TimeOnly,
+    TimeSpan,
     Guid,
+    Uri,
+    /// `byte[]`, which serializes as a base64 string
+    Bytes,
     Array(Box<CSharpType>),
+    /// Set collections, serialized as arrays of distinct items
+    Set(Box<CSharpType>),
     Nullable(Box<CSharpType>),
     Dictionary(Box<CSharpType>, Box<CSharpType>),
+    /// Serialized as an object with `Key` and `Value` properties
+    KeyValuePair(Box<CSharpType>, Box<CSharpType>),
     Custom(String),
+    Generic(String, Vec<CSharpType>),
+    Tuple(Vec<CSharpType>),
+    TypeParameter(String),
+    /// Type with a hand-written schema from `[type_mappings]`, along with
+    /// its type arguments by parameter name
+    Mapped(TypeMapping, Vec<(String, CSharpType)>),
+    /// Reference back into the schema's own reference cycle, resolved on use
+    Lazy(Box<CSharpType>),
+}
+
+/// A static class of constants, such as role or claim names
+#[derive(Debug)]
+struct CSharpConstants {
+    name: String,
+    fields: Vec<ConstantField>,
+    /// Nested static classes, emitted as nested objects
+    nested: Vec<CSharpConstants>,
+    documentation: Option<String>,
+}
+
+#[derive(Debug)]
+struct ConstantField {
+    name: String,
+    /// Value as a TypeScript literal
+    value: String,
+    documentation: Option<String>,
+}
+
+/// Value of a constant as far as it can be computed at generation time
+#[derive(Debug, Clone)]
+enum ConstantValue {
+    String(String),
+    Integer(i128),
+    /// Any other literal, already in TypeScript form
+    Literal(String),
 }
 
 #[derive(Debug)]
 struct EnumValue {
     name: String,
     display_name: Option<String>,
+    /// Explicit initializer as written in C#
+    expression: Option<Expr>,
+    /// Explicit or implicit numeric value, when it can be computed
+    value: Option<i128>,
     documentation: Option<String>,
 }
 
 #[derive(Debug)]
 struct CSharpEnum {
     name: String,
+    /// Name qualified with its namespace and enclosing types
+    full_name: String,
     values: Vec<EnumValue>,
+    /// Marked with `[JsonConverter(typeof(JsonStringEnumConverter))]`
+    serialized_as_string: bool,
+    is_flags: bool,
     documentation: Option<String>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct ValidationRule {
     rule_type: String,
     parameters: HashMap<String, String>,
     error_message: Option<String>,
-    condition: Option<String>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct DtoProperty {
     name: String,
     type_name: CSharpType,
     validations: Vec<ValidationRule>,
+    /// Parameter default or property initializer
+    default_value: Option<Expr>,
     documentation: Option<String>,
+    /// Declared where nullable annotations are disabled, so a reference
+    /// type may hold null without a `?`
+    oblivious: bool,
 }
 
 #[derive(Debug)]
 struct CSharpDto {
     name: String,
+    /// Name qualified with its namespace and enclosing types
+    full_name: String,
+    is_struct: bool,
+    type_parameters: Vec<String>,
+    base_type: Option<CSharpType>,
+    /// Further schemas merged in, used for interfaces with several bases
+    mixins: Vec<CSharpType>,
+    /// Interface schemas leave the type to the emitted `interface`
+    is_interface: bool,
+    properties: Vec<DtoProperty>,
+    documentation: Option<String>,
+}
+
+/// Enums and schema types declared anywhere in the project, by full name
+#[derive(Debug, Default)]
+struct TypeRegistry {
+    enums: HashMap<String, CSharpEnum>,
+    /// DTOs, and interfaces when they get schemas
+    schemas: HashSet<String>,
+    /// Schemas of value types, which are never null
+    structs: HashSet<String>,
+    /// Schemas taking part in a reference cycle, by cycle
+    cycles: HashMap<String, usize>,
+    /// Recursive schemas other schemas build on, which also export their
+    /// object schema for those to extend
+    object_schemas: HashSet<String>,
+    enum_style: EnumStyle,
+}
+
+/// What a type name refers to, as far as the project knows
+#[derive(Debug, Clone, Copy)]
+enum TypeClass<'a> {
+    Enum(&'a CSharpEnum),
+    Schema,
+    Unknown,
+}
+
+/// Types declared anywhere in the project, by full name, and the generated
+/// module each lands in, used to import references across files
+#[derive(Debug, Default)]
+struct SymbolTable {
+    symbols: HashMap<String, Symbol>,
+    output_root: PathBuf,
+}
+
+#[derive(Debug)]
+struct Symbol {
+    kind: SymbolKind,
+    /// Generated file without its `.ts` extension
+    module: PathBuf,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SymbolKind {
+    Schema,
+    /// Interface emitted as a TypeScript type only
+    Interface,
+    Enum,
+    /// Flags enum, which also exports a bitmask schema
+    FlagsEnum,
+    /// Enum emitted as a string union type, which schemas inline
+    UnionEnum,
+}
+
+/// Whether a reference comes from Zod schema code or a TypeScript type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SymbolUsage {
+    Schema,
+    Type,
+}
+
+/// Project-wide lookups shared by the files being generated
+struct ProjectContext<'a> {
+    docs: DocResolver<'a>,
+    types: TypeRegistry,
+    symbols: SymbolTable,
+}
+
+#[derive(Debug)]
+struct CSharpInterface {
+    name: String,
+    /// Name qualified with its namespace and enclosing types
+    full_name: String,
+    type_parameters: Vec<String>,
+    base_interfaces: Vec<CSharpType>,
     properties: Vec<DtoProperty>,
     documentation: Option<String>,
 }
@@ -155,6 +314,79 @@ fn generate_file_header(config: &Config, file_type: &str) -> String {
     header
 }
 
+/// Folder for a namespace with `prefix` removed, one kebab-case directory per
+/// segment: `UniTrack.Contracts.StudentGrades` becomes `student-grades`
+fn namespace_directory(namespace: &str, prefix: Option<&str>) -> PathBuf {
+    let relative = match prefix {
+        Some(prefix) if namespace == prefix => "",
+        Some(prefix) => namespace
+            .strip_prefix(prefix)
+            .and_then(|rest| rest.strip_prefix('.'))
+            .unwrap_or(namespace),
+        None => namespace,
+    };
+    relative
+        .split('.')
+        .filter(|segment| !segment.is_empty())
+        .map(to_kebab_case)
+        .collect()
+}
+
+/// Project-wide nullable context for a source file, read from the `<Nullable>`
+/// property of the nearest `.csproj` or `Directory.Build.props` above it.
+/// `enable` and `annotations` turn `?` annotations on. None when no project
+/// file sets it, as the context is then unknown
+fn nullable_context(path: &Path, cache: &mut HashMap<PathBuf, Option<bool>>) -> Option<bool> {
+    let dir = path.parent()?;
+    if let Some(&enabled) = cache.get(dir) {
+        return enabled;
+    }
+    let enabled = match project_nullable_setting(dir) {
+        Some(value) => {
+            Some(value.eq_ignore_ascii_case("enable") || value.eq_ignore_ascii_case("annotations"))
+        }
+        None => nullable_context(dir, cache),
+    };
+    cache.insert(dir.to_path_buf(), enabled);
+    enabled
+}
+
+/// `<Nullable>` value set by a project file in `dir` itself
+fn project_nullable_setting(dir: &Path) -> Option<String> {
+    let mut project_files: Vec<PathBuf> = fs::read_dir(dir)
+        .ok()?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "csproj"))
+        .collect();
+    project_files.sort();
+    project_files.push(dir.join("Directory.Build.props"));
+    project_files.iter().find_map(|path| {
+        let content = fs::read_to_string(path).ok()?;
+        let start = content.find("<Nullable>")? + "<Nullable>".len();
+        let end = content[start..].find("</Nullable>")?;
+        Some(content[start..start + end].trim().to_string())
+    })
+}
+
+fn to_kebab_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut result = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            let previous = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+            if previous.is_lowercase()
+                || previous.is_ascii_digit()
+                || (previous.is_uppercase() && next_is_lower)
+            {
+                result.push('-');
+            }
+        }
+        result.extend(c.to_lowercase());
+    }
+    result
+}
+
 impl FileProcessor {
     pub fn new() -> Self {
         Self {
@@ -184,10 +416,7 @@ impl FileProcessor {
     }
 
     pub fn register_output(&mut self, input: PathBuf, output: PathBuf) {
-        self.file_mapping
-            .entry(input)
-            .or_insert_with(Vec::new)
-            .push(output);
+        self.file_mapping.entry(input).or_default().push(output);
     }
 
     pub fn get_outputs_for_input(&self, input: &Path) -> Option<&Vec<PathBuf>> {
@@ -205,14 +434,46 @@ impl FileProcessor {
         Ok(())
     }
 
-    fn get_relative_output_path(
+    /// Generated file for `input_path` without its extension; the `.ts` and
+    /// `.schema.ts` outputs add theirs
+    fn output_base(
         &self,
         input_path: &Path,
         input_root: &Path,
         output_root: &Path,
+        namespace: Option<&str>,
+        config: &Config,
     ) -> PathBuf {
+        let file_name = input_path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .replace(".cs", "");
+        match config.output_layout {
+            OutputLayout::Directory => {
+                let relative = input_path.strip_prefix(input_root).unwrap_or(input_path);
+                output_root.join(relative).with_file_name(file_name)
+            }
+            OutputLayout::Namespace => output_root
+                .join(namespace_directory(
+                    namespace.unwrap_or_default(),
+                    config.namespace_prefix.as_deref(),
+                ))
+                .join(file_name),
+        }
+    }
+
+    /// Output base for a file whose name collides with another file's in the
+    /// same namespace folder, prefixed with its source folders:
+    /// `Students/Dtos.cs` becomes `Students.Dtos`
+    fn disambiguated_base(base: &Path, input_path: &Path, input_root: &Path) -> PathBuf {
         let relative = input_path.strip_prefix(input_root).unwrap_or(input_path);
-        output_root.join(relative)
+        let mut segments = path_segments(relative.parent().unwrap_or(Path::new("")));
+        segments.extend(
+            base.file_name()
+                .map(|name| name.to_string_lossy().into_owned()),
+        );
+        base.with_file_name(segments.join("."))
     }
 
     pub fn process_file(
@@ -222,56 +483,213 @@ impl FileProcessor {
         output_root: &Path,
         config: &Config,
     ) -> std::io::Result<()> {
-        if !self.should_process_file(input_path) {
-            self.stats.files_skipped += 1;
-            return Ok(());
-        }
-
-        self.stats.files_processed += 1;
-        self.cleanup_outputs(input_path)?;
-
-        let content = std::fs::read_to_string(input_path)?;
-
-        // Process enums
-        if let Ok(enums) = CSharpEnum::parse(&content) {
-            for enum_def in enums {
-                let relative_path =
-                    self.get_relative_output_path(input_path, input_root, output_root);
-                let output_dir = relative_path.parent().unwrap_or(output_root);
-                std::fs::create_dir_all(output_dir)?;
-
-                let output_path = output_dir.join(
-                    input_path
-                        .file_name()
-                        .unwrap()
-                        .to_string_lossy()
-                        .replace(".cs", ".ts"),
-                );
-                std::fs::write(&output_path, enum_def.to_typescript())?;
-                self.register_output(input_path.to_path_buf(), output_path);
-                self.stats.enums_generated += 1;
-            }
-        }
-
-        // Process DTOs
-        if let Ok(dtos) = CSharpDto::parse(&content) {
-            for dto in dtos {
-                let relative_path =
-                    self.get_relative_output_path(input_path, input_root, output_root);
-                let output_dir = relative_path.parent().unwrap_or(output_root);
-                std::fs::create_dir_all(output_dir)?;
-
-                let output_path = output_dir.join(
-                    input_path
-                        .file_name()
-                        .unwrap()
-                        .to_string_lossy()
-                        .replace(".cs", ".schema.ts"),
-                );
-                std::fs::write(&output_path, dto.to_zod_schema(&config))?;
-                self.register_output(input_path.to_path_buf(), output_path);
-                self.stats.schemas_generated += 1;
+        self.process_files(&[input_path.to_path_buf()], input_root, output_root, config)
+    }
+
+    /// Parses `paths` as one project so that partial types split across
+    /// files are merged and generated once, from the file declaring them first.
+    /// Nothing is regenerated unless some file changed since the last run.
+    pub fn process_files(
+        &mut self,
+        paths: &[PathBuf],
+        input_root: &Path,
+        output_root: &Path,
+        config: &Config,
+    ) -> std::io::Result<()> {
+        // Files gone since the last run take their outputs with them
+        let removed: Vec<PathBuf> = self
+            .file_hashes
+            .keys()
+            .filter(|path| !paths.contains(path))
+            .cloned()
+            .collect();
+        for path in &removed {
+            self.cleanup_outputs(path)?;
+            self.file_hashes.remove(path);
+            self.file_mapping.remove(path);
+        }
+
+        let mut files = Vec::new();
+        let mut units = Vec::new();
+        let mut nullable_contexts = HashMap::new();
+        let mut project_changed = !removed.is_empty();
+        for path in paths {
+            let changed = self.should_process_file(path);
+            project_changed |= changed;
+            let content = std::fs::read_to_string(path)?;
+            match parser::parse(&content, &config.defined_symbols) {
+                Ok(mut unit) => {
+                    // `#nullable` directives in the file override the project
+                    // setting. Without either, unannotated reference types
+                    // keep mapping to non-nullable schemas
+                    let source = path.canonicalize().unwrap_or_else(|_| path.clone());
+                    if let Some(enabled) = nullable_context(&source, &mut nullable_contexts) {
+                        syntax::apply_nullable_default(&mut unit, enabled);
+                    }
+                    files.push(path.as_path());
+                    units.push(unit);
+                }
+                Err(e) if changed => {
+                    self.stats.files_processed += 1;
+                    self.cleanup_outputs(path)?;
+                    eprintln!("{}: {}: {}", "Parse error".red(), path.display(), e);
+                }
+                Err(_) => self.stats.files_skipped += 1,
+            }
+        }
+
+        // Aliases are scoped to their file, so they are resolved before
+        // partial types move between units
+        syntax::resolve_using_aliases(&mut units);
+        syntax::merge_partial_types(&mut units);
+        for (reference, candidates) in syntax::resolve_type_references(&mut units) {
+            eprintln!(
+                "{}: {} may refer to any of {}; add a using directive or qualify it",
+                "Warning".yellow(),
+                reference,
+                candidates.join(", ")
+            );
+        }
+
+        // Namespace folders are chosen per type, so a file declaring types in
+        // several namespaces is generated once per namespace
+        if config.output_layout == OutputLayout::Namespace {
+            let mut split_files = Vec::new();
+            let mut split_units = Vec::new();
+            for (file, unit) in files.into_iter().zip(units) {
+                for part in unit.split_by_namespace() {
+                    split_files.push(file);
+                    split_units.push(part);
+                }
+            }
+            files = split_files;
+            units = split_units;
+        }
+        let docs = DocResolver::new(&units);
+        let types = TypeRegistry::new(&units, &docs, config);
+
+        let mut output_bases: Vec<PathBuf> = files
+            .iter()
+            .zip(&units)
+            .map(|(path, unit)| {
+                self.output_base(
+                    path,
+                    input_root,
+                    output_root,
+                    unit.namespace().as_deref(),
+                    config,
+                )
+            })
+            .collect();
+        let colliding: Vec<usize> = (0..files.len())
+            .filter(|&i| {
+                (0..files.len()).any(|j| files[i] != files[j] && output_bases[i] == output_bases[j])
+            })
+            .collect();
+        for i in colliding {
+            let base = Self::disambiguated_base(&output_bases[i], files[i], input_root);
+            eprintln!(
+                "{}: {} shares its output name with another file, generating {} instead",
+                "Warning".yellow(),
+                files[i].display(),
+                base.display()
+            );
+            output_bases[i] = base;
+        }
+
+        let mut symbols = SymbolTable::new(output_root);
+        for (unit, output_base) in units.iter().zip(&output_bases) {
+            symbols.register(unit, output_base, &docs, config);
+        }
+        let project = ProjectContext {
+            docs,
+            types,
+            symbols,
+        };
+
+        // A change to one file can alter what others import or how they refer
+        // to its types, such as a type changing module, an enum switching
+        // style or a reference cycle closing, so any change regenerates all.
+        // Files split by namespace are cleaned up and counted once.
+        let mut seen: Vec<&Path> = Vec::new();
+        for ((path, unit), output_base) in files.into_iter().zip(&units).zip(&output_bases) {
+            let first_part = !seen.contains(&path);
+            if first_part {
+                seen.push(path);
             }
+            if !project_changed {
+                if first_part {
+                    self.stats.files_skipped += 1;
+                }
+                continue;
+            }
+            if first_part {
+                self.stats.files_processed += 1;
+                self.cleanup_outputs(path)?;
+            }
+            self.generate(path, unit, &project, output_base, config)?;
+        }
+
+        Ok(())
+    }
+
+    fn generate(
+        &mut self,
+        input_path: &Path,
+        unit: &CompilationUnit,
+        project: &ProjectContext,
+        output_base: &Path,
+        config: &Config,
+    ) -> std::io::Result<()> {
+        let ProjectContext {
+            docs,
+            types: type_registry,
+            symbols,
+        } = project;
+        let output_dir = output_base.parent().unwrap_or(Path::new(""));
+
+        // Process enums and constant classes, which share the plain TypeScript file
+        let enums = CSharpEnum::parse(unit, docs, config);
+        let constants = CSharpConstants::parse(unit, docs, config);
+        if !enums.is_empty() || !constants.is_empty() {
+            std::fs::create_dir_all(output_dir)?;
+            let output_path = with_suffix(output_base, ".ts");
+            std::fs::write(
+                &output_path,
+                CSharpEnum::to_typescript_file(&enums, &constants, config),
+            )?;
+            self.register_output(input_path.to_path_buf(), output_path);
+            self.stats.enums_generated += enums.len();
+            self.stats.constants_generated += constants.len();
+        }
+
+        // Process DTOs and interfaces, which share the schema file
+        let mut dtos = CSharpDto::parse(unit, docs, config);
+        let interfaces = CSharpInterface::parse(unit, docs, config);
+        if config.interface_schemas {
+            dtos.extend(interfaces.iter().map(CSharpInterface::to_dto));
+        }
+        let dtos: Vec<CSharpDto> = dtos
+            .into_iter()
+            .map(|dto| dto.defer_cyclic_references(type_registry))
+            .collect();
+        self.stats.schemas_generated += dtos.len();
+        self.stats.interfaces_generated += interfaces.len();
+        if !dtos.is_empty() || !interfaces.is_empty() {
+            std::fs::create_dir_all(output_dir)?;
+            let output_path = with_suffix(output_base, ".schema.ts");
+            std::fs::write(
+                &output_path,
+                CSharpDto::to_zod_schema_file(
+                    &dtos,
+                    &interfaces,
+                    type_registry,
+                    symbols,
+                    output_dir,
+                    config,
+                ),
+            )?;
+            self.register_output(input_path.to_path_buf(), output_path);
         }
 
         Ok(())
@@ -279,41 +697,285 @@ impl FileProcessor {
 }
 
 impl CSharpType {
-    fn from_string(type_str: &str) -> Self {
-        match type_str {
-            "string" => CSharpType::String,
-            "int" | "Int32" => CSharpType::Int,
-            "double" | "Double" => CSharpType::Double,
-            "decimal" | "Decimal" => CSharpType::Decimal,
-            "bool" | "Boolean" => CSharpType::Bool,
-            "DateTime" => CSharpType::DateTime,
-            "Guid" => CSharpType::Guid,
-            s if s.starts_with("List<") || s.starts_with("IEnumerable<") => {
-                let inner = s[s.find('<').unwrap() + 1..s.find('>').unwrap()].trim();
-                CSharpType::Array(Box::new(CSharpType::from_string(inner)))
-            }
-            s if s.starts_with("Dictionary<") => {
-                let content = &s[s.find('<').unwrap() + 1..s.find('>').unwrap()];
-                let mut parts = content.split(',');
-                let key = parts.next().unwrap().trim();
-                let value = parts.next().unwrap().trim();
-                CSharpType::Dictionary(
-                    Box::new(CSharpType::from_string(key)),
-                    Box::new(CSharpType::from_string(value)),
-                )
+    fn from_syntax(syntax: &TypeSyntax) -> Self {
+        CSharpType::from_mapped_syntax(syntax, &BTreeMap::new())
+    }
+
+    /// Like `from_syntax`, with configured type mappings taking precedence
+    /// over the built-in ones
+    fn from_mapped_syntax(syntax: &TypeSyntax, mappings: &BTreeMap<String, TypeMapping>) -> Self {
+        let from_syntax = |syntax| CSharpType::from_mapped_syntax(syntax, mappings);
+        match syntax {
+            TypeSyntax::Named {
+                qualifier,
+                name,
+                arguments,
+            } => {
+                let mut arguments = arguments.iter().map(from_syntax);
+                if let Some((mapping, parameters)) =
+                    find_type_mapping(mappings, qualifier, name, arguments.len())
+                {
+                    return CSharpType::Mapped(
+                        mapping.clone(),
+                        parameters.into_iter().zip(arguments).collect(),
+                    );
+                }
+                match (name.as_str(), arguments.len()) {
+                    ("string" | "String", 0) => CSharpType::String,
+                    ("char" | "Char", 0) => CSharpType::Char,
+                    (name, 0) if integral_type_layout(name).is_some() => {
+                        let (bits, signed) = integral_type_layout(name).unwrap();
+                        CSharpType::Integer { bits, signed }
+                    }
+                    ("double" | "Double" | "float" | "Single" | "Half", 0) => CSharpType::Double,
+                    ("decimal" | "Decimal", 0) => CSharpType::Decimal,
+                    ("bool" | "Boolean", 0) => CSharpType::Bool,
+                    ("DateTime", 0) => CSharpType::DateTime,
+                    ("DateTimeOffset", 0) => CSharpType::DateTimeOffset,
+                    ("DateOnly", 0) => CSharpType::DateOnly,
+                    ("TimeOnly", 0) => CSharpType::TimeOnly,
+                    ("TimeSpan", 0) => CSharpType::TimeSpan,
+                    ("Guid", 0) => CSharpType::Guid,
+                    ("Uri", 0) => CSharpType::Uri,
+                    (name, 1) if is_list_type(name) => {
+                        CSharpType::Array(Box::new(arguments.next().unwrap()))
+                    }
+                    (name, 1) if is_set_type(name) => {
+                        CSharpType::Set(Box::new(arguments.next().unwrap()))
+                    }
+                    ("Nullable", 1) => CSharpType::Nullable(Box::new(arguments.next().unwrap())),
+                    (name, 2) if is_dictionary_type(name) => CSharpType::Dictionary(
+                        Box::new(arguments.next().unwrap()),
+                        Box::new(arguments.next().unwrap()),
+                    ),
+                    ("KeyValuePair", 2) => CSharpType::KeyValuePair(
+                        Box::new(arguments.next().unwrap()),
+                        Box::new(arguments.next().unwrap()),
+                    ),
+                    ("Tuple" | "ValueTuple", 1..) => CSharpType::Tuple(arguments.collect()),
+                    (_, 0) => CSharpType::Custom(qualified_name(qualifier, name)),
+                    _ => CSharpType::Generic(qualified_name(qualifier, name), arguments.collect()),
+                }
+            }
+            // Jagged and multi-dimensional arrays both serialize as nested arrays
+            TypeSyntax::Array(element, 1)
+                if matches!(
+                    from_syntax(element),
+                    CSharpType::Integer {
+                        bits: 8,
+                        signed: false
+                    }
+                ) =>
+            {
+                CSharpType::Bytes
+            }
+            TypeSyntax::Array(element, rank) => {
+                let mut array = from_syntax(element);
+                for _ in 0..*rank {
+                    array = CSharpType::Array(Box::new(array));
+                }
+                array
+            }
+            TypeSyntax::Nullable(inner) => CSharpType::Nullable(Box::new(from_syntax(inner))),
+            TypeSyntax::Tuple(elements) => {
+                CSharpType::Tuple(elements.iter().map(|e| from_syntax(&e.type_name)).collect())
+            }
+            TypeSyntax::Pointer(_) => CSharpType::Custom(syntax.to_string()),
+        }
+    }
+
+    /// Replaces references to the DTO's own type parameters with `TypeParameter`
+    fn bind_type_parameters(self, type_parameters: &[String]) -> Self {
+        let bind = |inner: Box<CSharpType>| Box::new(inner.bind_type_parameters(type_parameters));
+        match self {
+            CSharpType::Custom(name) if type_parameters.contains(&name) => {
+                CSharpType::TypeParameter(name)
+            }
+            CSharpType::Array(inner) => CSharpType::Array(bind(inner)),
+            CSharpType::Set(inner) => CSharpType::Set(bind(inner)),
+            CSharpType::Nullable(inner) => CSharpType::Nullable(bind(inner)),
+            CSharpType::Lazy(inner) => CSharpType::Lazy(bind(inner)),
+            CSharpType::Dictionary(key, value) => CSharpType::Dictionary(bind(key), bind(value)),
+            CSharpType::KeyValuePair(key, value) => {
+                CSharpType::KeyValuePair(bind(key), bind(value))
+            }
+            CSharpType::Tuple(elements) => CSharpType::Tuple(
+                elements
+                    .into_iter()
+                    .map(|e| e.bind_type_parameters(type_parameters))
+                    .collect(),
+            ),
+            CSharpType::Generic(name, arguments) => CSharpType::Generic(
+                name,
+                arguments
+                    .into_iter()
+                    .map(|a| a.bind_type_parameters(type_parameters))
+                    .collect(),
+            ),
+            CSharpType::Mapped(mapping, arguments) => CSharpType::Mapped(
+                mapping,
+                arguments
+                    .into_iter()
+                    .map(|(name, a)| (name, a.bind_type_parameters(type_parameters)))
+                    .collect(),
+            ),
+            other => other,
+        }
+    }
+
+    /// Switches 64-bit integers and decimals to the configured representation
+    fn with_precision(self, integers: Precision, decimals: Precision) -> Self {
+        let with = |inner: Box<CSharpType>| Box::new(inner.with_precision(integers, decimals));
+        match self {
+            CSharpType::Integer { bits: 64, signed } => match integers {
+                Precision::Number => self,
+                Precision::Bigint => CSharpType::BigInteger {
+                    signed,
+                    coerce: false,
+                },
+                Precision::CoercedBigint => CSharpType::BigInteger {
+                    signed,
+                    coerce: true,
+                },
+                Precision::String => CSharpType::NumericString {
+                    integral: true,
+                    signed,
+                },
+            },
+            // A bigint would drop the fraction, so decimals stay numbers then
+            CSharpType::Decimal => match decimals {
+                Precision::String => CSharpType::NumericString {
+                    integral: false,
+                    signed: true,
+                },
+                _ => self,
+            },
+            CSharpType::Array(inner) => CSharpType::Array(with(inner)),
+            CSharpType::Set(inner) => CSharpType::Set(with(inner)),
+            CSharpType::Nullable(inner) => CSharpType::Nullable(with(inner)),
+            CSharpType::Lazy(inner) => CSharpType::Lazy(with(inner)),
+            CSharpType::Dictionary(key, value) => CSharpType::Dictionary(with(key), with(value)),
+            CSharpType::KeyValuePair(key, value) => {
+                CSharpType::KeyValuePair(with(key), with(value))
+            }
+            CSharpType::Tuple(elements) => CSharpType::Tuple(
+                elements
+                    .into_iter()
+                    .map(|e| e.with_precision(integers, decimals))
+                    .collect(),
+            ),
+            CSharpType::Generic(name, arguments) => CSharpType::Generic(
+                name,
+                arguments
+                    .into_iter()
+                    .map(|a| a.with_precision(integers, decimals))
+                    .collect(),
+            ),
+            CSharpType::Mapped(mapping, arguments) => CSharpType::Mapped(
+                mapping,
+                arguments
+                    .into_iter()
+                    .map(|(name, a)| (name, a.with_precision(integers, decimals)))
+                    .collect(),
+            ),
+            other => other,
+        }
+    }
+
+    /// Wraps references to types `is_cyclic` accepts in `Lazy`, so schemas
+    /// in a reference cycle don't read each other before they are defined
+    fn defer_references(self, is_cyclic: &impl Fn(&str) -> bool) -> Self {
+        let defer = |inner: Box<CSharpType>| Box::new(inner.defer_references(is_cyclic));
+        match self {
+            CSharpType::Custom(ref name) | CSharpType::Generic(ref name, _) if is_cyclic(name) => {
+                CSharpType::Lazy(Box::new(self))
             }
-            s if s.ends_with('?') => {
-                let base_type = &s[..s.len() - 1];
-                CSharpType::Nullable(Box::new(CSharpType::from_string(base_type)))
+            CSharpType::Array(inner) => CSharpType::Array(defer(inner)),
+            CSharpType::Set(inner) => CSharpType::Set(defer(inner)),
+            CSharpType::Nullable(inner) => CSharpType::Nullable(defer(inner)),
+            CSharpType::Dictionary(key, value) => CSharpType::Dictionary(defer(key), defer(value)),
+            CSharpType::KeyValuePair(key, value) => {
+                CSharpType::KeyValuePair(defer(key), defer(value))
             }
-            s => CSharpType::Custom(s.to_string()),
+            CSharpType::Tuple(elements) => CSharpType::Tuple(
+                elements
+                    .into_iter()
+                    .map(|e| e.defer_references(is_cyclic))
+                    .collect(),
+            ),
+            CSharpType::Generic(name, arguments) => CSharpType::Generic(
+                name,
+                arguments
+                    .into_iter()
+                    .map(|a| a.defer_references(is_cyclic))
+                    .collect(),
+            ),
+            CSharpType::Mapped(mapping, arguments) => CSharpType::Mapped(
+                mapping,
+                arguments
+                    .into_iter()
+                    .map(|(name, a)| (name, a.defer_references(is_cyclic)))
+                    .collect(),
+            ),
+            other => other,
+        }
+    }
+
+    /// Name of the generated schema this type refers to, if it is user-defined
+    fn schema_name(&self) -> Option<&str> {
+        match self {
+            CSharpType::Custom(name) | CSharpType::Generic(name, _) => Some(name),
+            _ => None,
+        }
+    }
+
+    /// Expression evaluating to the schema of a user-defined type; localized
+    /// schemas are functions and get called
+    fn schema_reference(&self, types: &TypeRegistry, localized: bool) -> String {
+        match self {
+            CSharpType::Generic(name, arguments) => format!(
+                "{}Schema({})",
+                simple_name(name),
+                arguments
+                    .iter()
+                    .map(|a| a.to_zod_schema(types, localized))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            CSharpType::Custom(name) => match types.classify(name) {
+                TypeClass::Enum(csharp_enum) => csharp_enum.to_zod_reference(types.enum_style),
+                _ if localized => format!("{}Schema()", simple_name(name)),
+                _ => format!("{}Schema", simple_name(name)),
+            },
+            CSharpType::TypeParameter(name) => type_parameter_argument(name),
+            other => other.to_zod_schema(types, localized),
         }
     }
 
-    fn to_zod_type(&self, localized: bool, is_update_dto: bool) -> String {
-        let base_type = match self {
+    /// Schema for the type itself, as used for elements and type arguments
+    fn to_zod_schema(&self, types: &TypeRegistry, localized: bool) -> String {
+        match self {
             CSharpType::String => "z.string()".to_string(),
-            CSharpType::Int => "z.number().int()".to_string(),
+            CSharpType::Char => "z.string().length(1)".to_string(),
+            CSharpType::Integer { bits, signed } => integer_schema(*bits, *signed),
+            CSharpType::BigInteger { signed, coerce } => {
+                let (min, max) = if *signed {
+                    (i64::MIN as i128, i64::MAX as i128)
+                } else {
+                    (0, u64::MAX as i128)
+                };
+                format!(
+                    "z{}.bigint().min({}n).max({}n)",
+                    if *coerce { ".coerce" } else { "" },
+                    min,
+                    max
+                )
+            }
+            CSharpType::NumericString { integral, signed } => {
+                let sign = if *signed { "-?" } else { "" };
+                let fraction = if *integral { "" } else { "(\\.\\d+)?" };
+                format!("z.string().regex(/^{}\\d+{}$/)", sign, fraction)
+            }
             CSharpType::Double | CSharpType::Decimal => "z.number()".to_string(),
             CSharpType::Bool => "z.boolean()".to_string(),
             CSharpType::Guid => "z.string().uuid()".to_string(),
@@ -324,30 +986,271 @@ impl CSharpType {
                     "z.string().datetime()".to_string()
                 }
             }
-            CSharpType::Array(inner) => {
-                format!("z.array({})", inner.to_zod_type(localized, is_update_dto))
-            }
-            CSharpType::Nullable(inner) => {
-                format!("{}.nullable()", inner.to_zod_type(localized, is_update_dto))
+            CSharpType::DateTimeOffset => {
+                if localized {
+                    "z.date().or(z.string().datetime({ offset: true }))".to_string()
+                } else {
+                    "z.string().datetime({ offset: true })".to_string()
+                }
             }
+            CSharpType::DateOnly => "z.string().date()".to_string(),
+            CSharpType::TimeOnly => "z.string().time()".to_string(),
+            CSharpType::TimeSpan => "z.string().duration()".to_string(),
+            CSharpType::Uri => "z.string().url()".to_string(),
+            CSharpType::Bytes => "z.string().base64()".to_string(),
+            CSharpType::Array(inner) => format!("z.array({})", inner.to_zod_schema(types, localized)),
+            CSharpType::Set(inner) => format!(
+                "z.array({}).refine((items) => new Set(items).size === items.length, {{ message: 'Items must be unique' }})",
+                inner.to_zod_schema(types, localized)
+            ),
+            CSharpType::Nullable(inner) => format!("{}.nullable()", inner.to_zod_schema(types, localized)),
             CSharpType::Dictionary(key, value) => format!(
                 "z.record({}, {})",
-                key.to_zod_type(localized, is_update_dto),
-                value.to_zod_type(localized, is_update_dto)
+                key.to_record_key_schema(types),
+                value.to_zod_schema(types, localized)
             ),
-            CSharpType::Custom(name) => format!("{}Schema", name),
-        };
+            CSharpType::KeyValuePair(key, value) => format!(
+                "z.object({{ Key: {}, Value: {} }})",
+                key.to_zod_schema(types, localized),
+                value.to_zod_schema(types, localized)
+            ),
+            CSharpType::Tuple(elements) => format!(
+                "z.tuple([{}])",
+                elements
+                    .iter()
+                    .map(|e| e.to_zod_schema(types, localized))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            // Only schemas known to be generated are localized functions
+            CSharpType::Custom(name) => self.schema_reference(
+                types,
+                localized && matches!(types.classify(name), TypeClass::Schema),
+            ),
+            CSharpType::TypeParameter(_) => self.schema_reference(types, false),
+            CSharpType::Generic(..) => self.schema_reference(types, localized),
+            CSharpType::Mapped(mapping, arguments) => {
+                fill_type_parameters(&mapping.zod, arguments, |a| a.to_zod_schema(types, localized))
+            }
+            CSharpType::Lazy(inner) => {
+                format!("z.lazy(() => {})", inner.to_zod_schema(types, localized))
+            }
+        }
+    }
 
-        // Make all fields required by default for create DTOs, optional for update DTOs
-        if is_update_dto {
-            format!("{}.optional()", base_type)
-        } else {
-            format!("{}.required()", base_type)
+    /// Schema for dictionary keys, which always arrive as JSON property names
+    fn to_record_key_schema(&self, types: &TypeRegistry) -> String {
+        match self {
+            CSharpType::Integer { .. } | CSharpType::Double | CSharpType::Decimal => self
+                .to_zod_schema(types, false)
+                .replacen("z.number()", "z.coerce.number()", 1),
+            other => other.to_zod_schema(types, false),
+        }
+    }
+
+    /// Names of the user-defined types this type refers to; `through_lazy`
+    /// includes those only read once the schema is in use
+    fn collect_references<'a>(&'a self, names: &mut Vec<&'a str>, through_lazy: bool) {
+        match self {
+            CSharpType::Custom(name) => names.push(name),
+            CSharpType::Generic(name, arguments) => {
+                names.push(name);
+                for argument in arguments {
+                    argument.collect_references(names, through_lazy);
+                }
+            }
+            CSharpType::Array(inner) | CSharpType::Set(inner) | CSharpType::Nullable(inner) => {
+                inner.collect_references(names, through_lazy)
+            }
+            CSharpType::Dictionary(key, value) | CSharpType::KeyValuePair(key, value) => {
+                key.collect_references(names, through_lazy);
+                value.collect_references(names, through_lazy);
+            }
+            CSharpType::Tuple(elements) => {
+                for element in elements {
+                    element.collect_references(names, through_lazy);
+                }
+            }
+            CSharpType::Mapped(_, arguments) => {
+                for (_, argument) in arguments {
+                    argument.collect_references(names, through_lazy);
+                }
+            }
+            CSharpType::Lazy(inner) if through_lazy => inner.collect_references(names, true),
+            _ => {}
+        }
+    }
+
+    /// Imports required by configured type mappings anywhere in this type
+    fn collect_mapped_imports<'a>(&'a self, imports: &mut Vec<&'a ImportConfig>) {
+        match self {
+            CSharpType::Mapped(mapping, arguments) => {
+                if let Some(import) = &mapping.import {
+                    if !imports.contains(&import) {
+                        imports.push(import);
+                    }
+                }
+                for (_, argument) in arguments {
+                    argument.collect_mapped_imports(imports);
+                }
+            }
+            CSharpType::Array(inner)
+            | CSharpType::Set(inner)
+            | CSharpType::Nullable(inner)
+            | CSharpType::Lazy(inner) => inner.collect_mapped_imports(imports),
+            CSharpType::Dictionary(key, value) | CSharpType::KeyValuePair(key, value) => {
+                key.collect_mapped_imports(imports);
+                value.collect_mapped_imports(imports);
+            }
+            CSharpType::Tuple(arguments) | CSharpType::Generic(_, arguments) => {
+                for argument in arguments {
+                    argument.collect_mapped_imports(imports);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Whether C# values of this type are references, which may be null when
+    /// nullable annotations are disabled. Types the project doesn't declare
+    /// are assumed to be classes.
+    fn is_reference_type(&self, types: &TypeRegistry) -> bool {
+        match self {
+            CSharpType::String
+            | CSharpType::Uri
+            | CSharpType::Bytes
+            | CSharpType::Array(_)
+            | CSharpType::Set(_)
+            | CSharpType::Dictionary(..) => true,
+            CSharpType::Custom(name) | CSharpType::Generic(name, _) => match types.classify(name) {
+                TypeClass::Enum(_) => false,
+                TypeClass::Schema => !types.structs.contains(name),
+                TypeClass::Unknown => true,
+            },
+            CSharpType::Lazy(inner) => inner.is_reference_type(types),
+            _ => false,
+        }
+    }
+
+    /// Types whose constants can be written as TypeScript literals
+    fn is_primitive(&self) -> bool {
+        matches!(
+            self,
+            CSharpType::String
+                | CSharpType::Char
+                | CSharpType::Integer { .. }
+                | CSharpType::Double
+                | CSharpType::Decimal
+                | CSharpType::Bool
+        )
+    }
+
+    /// TypeScript type annotation, as used by generated interfaces
+    fn to_typescript_type(&self) -> String {
+        match self {
+            CSharpType::String
+            | CSharpType::Char
+            | CSharpType::Guid
+            | CSharpType::DateTime
+            | CSharpType::DateTimeOffset
+            | CSharpType::DateOnly
+            | CSharpType::TimeOnly
+            | CSharpType::TimeSpan
+            | CSharpType::Uri
+            | CSharpType::Bytes
+            | CSharpType::NumericString { .. } => "string".to_string(),
+            CSharpType::BigInteger { .. } => "bigint".to_string(),
+            CSharpType::Integer { .. } | CSharpType::Double | CSharpType::Decimal => {
+                "number".to_string()
+            }
+            CSharpType::Bool => "boolean".to_string(),
+            CSharpType::Array(inner) | CSharpType::Set(inner) => match inner.as_ref() {
+                CSharpType::Nullable(_) => format!("({})[]", inner.to_typescript_type()),
+                inner => format!("{}[]", inner.to_typescript_type()),
+            },
+            CSharpType::Nullable(inner) => format!("{} | null", inner.to_typescript_type()),
+            CSharpType::Dictionary(key, value) => format!(
+                "Record<{}, {}>",
+                key.to_typescript_type(),
+                value.to_typescript_type()
+            ),
+            CSharpType::KeyValuePair(key, value) => format!(
+                "{{ Key: {}; Value: {} }}",
+                key.to_typescript_type(),
+                value.to_typescript_type()
+            ),
+            CSharpType::Tuple(elements) => format!(
+                "[{}]",
+                elements
+                    .iter()
+                    .map(|e| e.to_typescript_type())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            CSharpType::Custom(name) => simple_name(name).to_string(),
+            CSharpType::TypeParameter(name) => name.clone(),
+            CSharpType::Generic(name, arguments) => format!(
+                "{}<{}>",
+                simple_name(name),
+                arguments
+                    .iter()
+                    .map(|a| a.to_typescript_type())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            CSharpType::Mapped(mapping, arguments) => {
+                fill_type_parameters(&mapping.typescript, arguments, |a| a.to_typescript_type())
+            }
+            CSharpType::Lazy(inner) => inner.to_typescript_type(),
         }
     }
 }
 
 impl ValidationRule {
+    fn from_attribute(attribute: &Attribute) -> Option<Self> {
+        let rule_type = [
+            "Required",
+            "Range",
+            "StringLength",
+            "EmailAddress",
+            "Phone",
+            "RegularExpression",
+        ]
+        .into_iter()
+        .find(|name| attribute.is(name))?;
+
+        // Positional constructor arguments, keyed the way `to_zod_validation` reads them
+        let positional: &[&str] = match rule_type {
+            "Range" if attribute.positional(2).is_some() => &["Type", "Minimum", "Maximum"],
+            "Range" => &["Minimum", "Maximum"],
+            "StringLength" => &["MaximumLength"],
+            "RegularExpression" => &["pattern"],
+            _ => &[],
+        };
+
+        let mut parameters = HashMap::new();
+        for (index, key) in positional.iter().enumerate() {
+            if let Some(value) = attribute.positional(index) {
+                parameters.insert(key.to_string(), value.as_text());
+            }
+        }
+        for argument in &attribute.arguments {
+            if let Some(name) = &argument.name {
+                parameters.insert(name.clone(), argument.value.as_text());
+            }
+        }
+
+        Some(Self {
+            rule_type: rule_type.to_string(),
+            error_message: parameters.remove("ErrorMessage"),
+            parameters,
+        })
+    }
+
+    fn message(&self, default: String) -> String {
+        ts_string(self.error_message.as_ref().unwrap_or(&default))
+    }
+
     fn to_zod_validation(&self, prop_name: &str, localized: bool) -> Option<String> {
         match self.rule_type.as_str() {
             "Required" => Some(".required()".to_string()),
@@ -357,9 +1260,7 @@ impl ValidationRule {
                 let error_msg = if localized {
                     format!("t('{}.range')", prop_name)
                 } else {
-                    self.error_message
-                        .clone()
-                        .unwrap_or_else(|| format!("Value must be between {} and {}", min, max))
+                    self.message(format!("Value must be between {} and {}", min, max))
                 };
                 Some(format!(
                     ".min({}, {{ message: {} }}).max({}, {{ message: {} }})",
@@ -372,9 +1273,7 @@ impl ValidationRule {
                     let error_msg = if localized {
                         format!("t('{}.minLength')", prop_name)
                     } else {
-                        self.error_message
-                            .clone()
-                            .unwrap_or_else(|| format!("Minimum length is {}", min))
+                        self.message(format!("Minimum length is {}", min))
                     };
                     validation.push_str(&format!(".min({}, {{ message: {} }})", min, error_msg));
                 }
@@ -382,9 +1281,7 @@ impl ValidationRule {
                     let error_msg = if localized {
                         format!("t('{}.maxLength')", prop_name)
                     } else {
-                        self.error_message
-                            .clone()
-                            .unwrap_or_else(|| format!("Maximum length is {}", max))
+                        self.message(format!("Maximum length is {}", max))
                     };
                     validation.push_str(&format!(".max({}, {{ message: {} }})", max, error_msg));
                 }
@@ -394,9 +1291,7 @@ impl ValidationRule {
                 let error_msg = if localized {
                     format!("t('{}.email')", prop_name)
                 } else {
-                    self.error_message
-                        .clone()
-                        .unwrap_or_else(|| "Invalid email address".to_string())
+                    self.message("Invalid email address".to_string())
                 };
                 Some(format!(".email({{ message: {} }})", error_msg))
             }
@@ -404,9 +1299,7 @@ impl ValidationRule {
                 let error_msg = if localized {
                     format!("t('{}.phone')", prop_name)
                 } else {
-                    self.error_message
-                        .clone()
-                        .unwrap_or_else(|| "Invalid phone number".to_string())
+                    self.message("Invalid phone number".to_string())
                 };
                 Some(format!(
                     ".regex(/^\\+?[1-9]\\d{{1,14}}$/, {{ message: {} }})",
@@ -418,13 +1311,12 @@ impl ValidationRule {
                     let error_msg = if localized {
                         format!("t('{}.pattern')", prop_name)
                     } else {
-                        self.error_message
-                            .clone()
-                            .unwrap_or_else(|| "Invalid format".to_string())
+                        self.message("Invalid format".to_string())
                     };
                     Some(format!(
-                        ".regex(new RegExp('{}'), {{ message: {} }})",
-                        pattern, error_msg
+                        ".regex(new RegExp({}), {{ message: {} }})",
+                        ts_string(pattern),
+                        error_msg
                     ))
                 } else {
                     None
@@ -435,77 +1327,363 @@ impl ValidationRule {
     }
 }
 
-impl CSharpEnum {
-    fn parse(content: &str) -> Result<Vec<Self>, &'static str> {
-        let mut enums = Vec::new();
-
-        // Extract documentation if present
-        let get_documentation = |text: &str| -> Option<String> {
-            DOC_COMMENT_REGEX
-                .captures(text)
-                .map(|cap| cap[1].trim().to_string())
-        };
-
-        for enum_match in ENUM_REGEX.captures_iter(content) {
-            let name = enum_match.name("name").unwrap().as_str().to_string();
-            let body = enum_match.name("body").unwrap().as_str();
-
-            // Get documentation before the enum
-            let documentation = get_documentation(
-                &content[..enum_match.get(0).unwrap().start()]
-                    .lines()
-                    .rev()
-                    .take(3)
-                    .collect::<Vec<_>>()
-                    .join("\n"),
-            );
+/// Formats `value` as a single-quoted TypeScript string literal
+fn ts_string(value: &str) -> String {
+    let mut literal = String::from("'");
+    for c in value.chars() {
+        match c {
+            '\'' => literal.push_str("\\'"),
+            '\\' => literal.push_str("\\\\"),
+            '\n' => literal.push_str("\\n"),
+            '\r' => literal.push_str("\\r"),
+            '\t' => literal.push_str("\\t"),
+            c => literal.push(c),
+        }
+    }
+    literal.push('\'');
+    literal
+}
 
-            let values = body
-                .split(',')
-                .map(|line| line.trim())
-                .filter(|line| !line.is_empty())
-                .map(|line| {
-                    let display_name = DISPLAY_ATTR_REGEX
-                        .captures(line)
-                        .map(|cap| cap[1].to_string());
+/// Factory parameter holding the schema for a type parameter: `T` becomes
+/// `item`, `TError` becomes `error`
+fn type_parameter_argument(name: &str) -> String {
+    if name == "T" {
+        return "item".to_string();
+    }
+    let name = match name.strip_prefix('T') {
+        Some(rest) if rest.starts_with(|c: char| c.is_ascii_uppercase()) => rest,
+        _ => name,
+    };
+    name[..1].to_lowercase() + &name[1..]
+}
 
-                    let name = line.split_whitespace().last().unwrap().to_string();
+/// Type name without its namespace and enclosing types
+fn simple_name(full_name: &str) -> &str {
+    full_name.rsplit('.').next().unwrap_or(full_name)
+}
 
-                    // Get documentation for enum value
-                    let documentation = get_documentation(line);
+/// Name written as `qualifier.name`, which references to project types are
+/// resolved to in full
+fn qualified_name(qualifier: &[String], name: &str) -> String {
+    qualifier
+        .iter()
+        .map(String::as_str)
+        .chain([name])
+        .collect::<Vec<_>>()
+        .join(".")
+}
 
-                    EnumValue {
-                        name,
-                        display_name,
-                        documentation,
-                    }
-                })
-                .collect();
+/// C# convention: interfaces are named `IThing`
+fn is_interface_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next() == Some('I') && chars.next().is_some_and(|c| c.is_ascii_uppercase())
+}
 
-            enums.push(Self {
-                name,
-                values,
-                documentation,
-            });
+/// JSDoc comment for `doc`; single-line member docs stay on one line
+fn jsdoc(doc: &str, indent: &str, inline: bool) -> String {
+    let doc = doc.replace("*/", "*\\/");
+    if inline && !doc.contains('\n') {
+        return format!("{}/** {} */\n", indent, doc);
+    }
+    let mut output = format!("{}/**\n", indent);
+    for line in doc.lines() {
+        if line.is_empty() {
+            output.push_str(&format!("{} *\n", indent));
+        } else {
+            output.push_str(&format!("{} * {}\n", indent, line));
         }
+    }
+    output.push_str(&format!("{} */\n", indent));
+    output
+}
 
-        Ok(enums)
+/// JSDoc text for a declaration, with `[Obsolete("...")]` as `@deprecated`
+fn jsdoc_text(doc: Option<XmlDoc>, attributes: &[Attribute]) -> Option<String> {
+    let mut doc = doc.unwrap_or_default();
+    if let Some(obsolete) = attributes.iter().find(|a| a.is("Obsolete")) {
+        doc.deprecated = Some(
+            obsolete
+                .positional(0)
+                .map(Expr::as_text)
+                .unwrap_or_default(),
+        );
     }
+    doc.to_jsdoc()
+}
 
-    fn to_typescript(&self) -> String {
-        let mut output = String::new();
+/// Declarations left out of the output when `drop_obsolete` is set
+fn is_dropped(attributes: &[Attribute], config: &Config) -> bool {
+    config.drop_obsolete && attributes.iter().any(|a| a.is("Obsolete"))
+}
 
-        // Add generated header
-        output.push_str(&generate_file_header(
-            &Config::load().unwrap_or_default(),
-            "Enum",
-        ));
+/// Documentation lookup for the declarations of the project, following
+/// `<inheritdoc/>` to base types in any file
+struct DocResolver<'a> {
+    types: HashMap<&'a str, &'a TypeDecl>,
+}
+
+impl<'a> DocResolver<'a> {
+    fn new(units: &'a [CompilationUnit]) -> Self {
+        Self {
+            types: units
+                .iter()
+                .flat_map(CompilationUnit::types)
+                .map(|decl| (decl.full_name.as_str(), decl))
+                .collect(),
+        }
+    }
+
+    /// Declaration named `name` in full, or else the only one whose full
+    /// name ends with it, as `cref`s may be written
+    fn find(&self, name: &str) -> Option<&'a TypeDecl> {
+        if let Some(decl) = self.types.get(name) {
+            return Some(decl);
+        }
+        let suffix = format!(".{}", name);
+        let mut candidates = self
+            .types
+            .iter()
+            .filter(|(full_name, _)| full_name.ends_with(&suffix));
+        match (candidates.next(), candidates.next()) {
+            (Some((_, decl)), None) => Some(decl),
+            _ => None,
+        }
+    }
+
+    fn type_doc(&self, decl: &TypeDecl) -> Option<XmlDoc> {
+        self.type_doc_at(decl, 0)
+    }
+
+    fn type_doc_at(&self, decl: &TypeDecl, depth: usize) -> Option<XmlDoc> {
+        let doc = XmlDoc::parse(&decl.doc_comment()?);
+        Some(self.inherit(doc, decl, None, depth))
+    }
+
+    /// Docs of a property, field, enum member or positional parameter; the
+    /// latter are usually documented by a `<param>` on the type
+    fn member_doc(&self, decl: &TypeDecl, name: &str) -> Option<XmlDoc> {
+        self.member_doc_at(decl, name, 0)
+    }
+
+    fn member_doc_at(&self, decl: &TypeDecl, name: &str, depth: usize) -> Option<XmlDoc> {
+        let trivia = decl
+            .members
+            .iter()
+            .find_map(|member| match member {
+                MemberDecl::Property(p) if p.name == name => Some(&p.trivia),
+                MemberDecl::Field(f) if f.name == name => Some(&f.trivia),
+                MemberDecl::EnumMember(e) if e.name == name => Some(&e.trivia),
+                _ => None,
+            })
+            .or_else(|| {
+                decl.parameters
+                    .iter()
+                    .flatten()
+                    .find(|p| p.name.eq_ignore_ascii_case(name))
+                    .map(|p| &p.trivia)
+            });
+        let doc = match trivia.and_then(|trivia| syntax::doc_comment(trivia)) {
+            Some(comment) => XmlDoc::parse(&comment),
+            None => {
+                let type_doc = self.type_doc_at(decl, depth)?;
+                let param = type_doc
+                    .params
+                    .iter()
+                    .find(|(param, _)| param.eq_ignore_ascii_case(name))?;
+                XmlDoc {
+                    summary: param.1.clone(),
+                    ..XmlDoc::default()
+                }
+            }
+        };
+        Some(self.inherit(doc, decl, Some(name), depth))
+    }
+
+    /// Resolves `<inheritdoc/>` from the `cref` it names, or else from the
+    /// first documented base type
+    fn inherit(
+        &self,
+        mut doc: XmlDoc,
+        decl: &TypeDecl,
+        member: Option<&str>,
+        depth: usize,
+    ) -> XmlDoc {
+        // Guards against inheritance cycles in malformed code
+        if !doc.inherits || depth > 16 {
+            return doc;
+        }
+
+        let (sources, member) = match doc.inherit_cref.clone() {
+            Some(cref) => match self.find(&cref) {
+                Some(decl) => (vec![decl], member.map(str::to_string)),
+                None => match cref.rsplit_once('.') {
+                    Some((owner, name)) => (
+                        self.find(owner).into_iter().collect(),
+                        Some(name.to_string()),
+                    ),
+                    None => (Vec::new(), None),
+                },
+            },
+            None => (
+                decl.base_types
+                    .iter()
+                    .filter_map(|base| {
+                        CSharpType::from_syntax(base)
+                            .schema_name()
+                            .and_then(|name| self.types.get(name).copied())
+                    })
+                    .collect(),
+                member.map(str::to_string),
+            ),
+        };
+
+        let inherited = sources.into_iter().find_map(|source| match &member {
+            Some(name) => self.member_doc_at(source, name, depth + 1),
+            None => self.type_doc_at(source, depth + 1),
+        });
+        if let Some(inherited) = inherited {
+            doc.inherit_from(&inherited);
+        }
+        doc
+    }
+}
+
+impl CSharpEnum {
+    fn parse(unit: &CompilationUnit, docs: &DocResolver, config: &Config) -> Vec<Self> {
+        unit.types()
+            .into_iter()
+            .filter(|decl| decl.kind == TypeKind::Enum && decl.has_modifier("public"))
+            .filter(|decl| !is_dropped(&decl.attributes, config))
+            .map(|decl| Self::from_declaration(decl, docs, config))
+            .collect()
+    }
+
+    fn from_declaration(decl: &TypeDecl, docs: &DocResolver, config: &Config) -> Self {
+        let underlying_type = decl
+            .base_types
+            .first()
+            .map(|t| t.to_string())
+            .unwrap_or_else(|| "int".to_string());
+
+        // Members without an initializer continue from the previous value
+        let mut known: HashMap<String, i128> = HashMap::new();
+        let mut next = Some(0);
+        let mut values = Vec::new();
+        for member in decl.members.iter().filter_map(|member| match member {
+            MemberDecl::EnumMember(value) => Some(value),
+            _ => None,
+        }) {
+            let value = match &member.value {
+                Some(expr) => expr.evaluate(&|name| {
+                    let own_prefix = format!("{}.", decl.name);
+                    known
+                        .get(name.strip_prefix(&own_prefix).unwrap_or(name))
+                        .copied()
+                }),
+                None => next,
+            }
+            .map(|v| wrap_to_integral_type(v, &underlying_type));
+
+            if let Some(value) = value {
+                known.insert(member.name.clone(), value);
+            }
+            next = value.and_then(|v| v.checked_add(1));
+
+            // Dropped members still count towards the implicit values after them
+            if is_dropped(&member.attributes, config) {
+                continue;
+            }
+
+            values.push(EnumValue {
+                name: member.name.clone(),
+                display_name: member
+                    .attributes
+                    .iter()
+                    .find(|a| a.is("Display"))
+                    .and_then(|a| a.named("Name"))
+                    .map(Expr::as_text),
+                expression: member.value.clone(),
+                value,
+                documentation: jsdoc_text(docs.member_doc(decl, &member.name), &member.attributes),
+            });
+        }
+
+        let serialized_as_string = decl.attribute("JsonConverter").is_some_and(|a| {
+            a.positional(0)
+                .is_some_and(|arg| arg.to_string().contains("StringEnumConverter"))
+        });
+
+        Self {
+            name: decl.name.clone(),
+            full_name: decl.full_name.clone(),
+            values,
+            serialized_as_string,
+            is_flags: decl.attribute("Flags").is_some(),
+            documentation: jsdoc_text(docs.type_doc(decl), &decl.attributes),
+        }
+    }
+
+    fn style(&self, configured: EnumStyle) -> EnumStyle {
+        // Flags only make sense as numbers that can be combined
+        if self.is_flags {
+            EnumStyle::Numeric
+        } else if self.serialized_as_string && configured != EnumStyle::Union {
+            EnumStyle::String
+        } else {
+            configured
+        }
+    }
+
+    /// Schema for properties of this enum's type
+    fn to_zod_reference(&self, configured: EnumStyle) -> String {
+        match self.style(configured) {
+            _ if self.is_flags => format!("{}Schema", self.name),
+            EnumStyle::Union if self.values.is_empty() => "z.never()".to_string(),
+            EnumStyle::Union => format!(
+                "z.enum([{}])",
+                self.values
+                    .iter()
+                    .map(|value| ts_string(value.display_name.as_ref().unwrap_or(&value.name)))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            EnumStyle::String | EnumStyle::Numeric => format!("z.nativeEnum({})", self.name),
+        }
+    }
+
+    fn to_typescript_file(
+        enums: &[Self],
+        constants: &[CSharpConstants],
+        config: &Config,
+    ) -> String {
+        let file_type = if enums.is_empty() {
+            "Constants"
+        } else {
+            "Enum"
+        };
+        let mut output = generate_file_header(config, file_type);
+        if enums.iter().any(|e| e.is_flags) {
+            output.push_str("import { z } from 'zod';\n\n");
+        }
+        let bodies: Vec<String> = enums
+            .iter()
+            .map(|e| e.to_typescript(config))
+            .chain(constants.iter().map(CSharpConstants::to_typescript))
+            .collect();
+        output.push_str(&bodies.join("\n"));
+        output
+    }
+
+    fn to_typescript(&self, config: &Config) -> String {
+        let mut output = String::new();
 
-        // Rest of the implementation remains the same...
         if let Some(doc) = &self.documentation {
-            output.push_str("/**\n");
-            output.push_str(&format!(" * {}\n", doc));
-            output.push_str(" */\n");
+            output.push_str(&jsdoc(doc, "", false));
+        }
+
+        if self.style(config.enum_style) == EnumStyle::Union {
+            output.push_str(&self.to_union_type());
+            return output;
         }
 
         output.push_str(&format!("export enum {} {{\n", self.name));
@@ -513,202 +1691,3194 @@ impl CSharpEnum {
         for value in &self.values {
             // Add documentation for enum value if present
             if let Some(doc) = &value.documentation {
-                output.push_str(&format!("  /** {} */\n", doc));
+                output.push_str(&jsdoc(doc, "  ", true));
+            }
+
+            match self.style(config.enum_style) {
+                EnumStyle::String | EnumStyle::Union => output.push_str(&format!(
+                    "  {} = {},\n",
+                    value.name,
+                    ts_string(value.display_name.as_ref().unwrap_or(&value.name))
+                )),
+                EnumStyle::Numeric => match (value.value, &value.expression) {
+                    (Some(number), _) => {
+                        output.push_str(&format!("  {} = {},\n", value.name, number))
+                    }
+                    // Left for TypeScript to evaluate, e.g. references to other types
+                    (None, Some(expression)) => output.push_str(&format!(
+                        "  {} = {},\n",
+                        value.name,
+                        without_casts(expression)
+                    )),
+                    (None, None) => output.push_str(&format!("  {},\n", value.name)),
+                },
             }
+        }
+
+        output.push_str("}\n");
 
+        if self.is_flags {
+            output.push_str(&self.to_flag_helpers());
+        }
+
+        output
+    }
+
+    /// `export type Status = 'Active' | 'Inactive';`, keeping value docs
+    fn to_union_type(&self) -> String {
+        let mut output = format!("export type {} =", self.name);
+        if self.values.is_empty() {
+            output.push_str(" never;\n");
+            return output;
+        }
+        output.push('\n');
+        for value in &self.values {
+            if let Some(doc) = &value.documentation {
+                output.push_str(&jsdoc(doc, "  ", true));
+            }
             output.push_str(&format!(
-                "  {} = '{}',\n",
-                value.name,
-                value.display_name.as_ref().unwrap_or(&value.name)
+                "  | {}\n",
+                ts_string(value.display_name.as_ref().unwrap_or(&value.name))
             ));
         }
+        output.pop();
+        output.push_str(";\n");
+        output
+    }
+
+    /// Helpers merged into the enum as a namespace, plus a schema accepting
+    /// only combinations of known bits
+    fn to_flag_helpers(&self) -> String {
+        let single_bits: Vec<String> = self
+            .values
+            .iter()
+            .filter(|v| v.value.is_some_and(|n| n > 0 && n & (n - 1) == 0))
+            .map(|v| format!("{}.{}", self.name, v.name))
+            .collect();
+        let mask = self
+            .values
+            .iter()
+            .filter_map(|v| v.value)
+            .filter(|n| *n >= 0)
+            .fold(0, |mask, n| mask | n);
 
+        let mut output = String::new();
+        output.push_str(&format!("\nexport namespace {} {{\n", self.name));
+        output.push_str("  /** Whether every bit of `flag` is set in `value` */\n");
+        output.push_str(&format!(
+            "  export function hasFlag(value: number, flag: {}): boolean {{\n",
+            self.name
+        ));
+        output.push_str("    return ((value & flag) >>> 0) === flag;\n");
+        output.push_str("  }\n\n");
+        output.push_str("  /** Combines flags into a single value */\n");
+        output.push_str(&format!(
+            "  export function combineFlags(...flags: {}[]): number {{\n",
+            self.name
+        ));
+        output.push_str(
+            "    return flags.reduce<number>((combined, flag) => (combined | flag) >>> 0, 0);\n",
+        );
+        output.push_str("  }\n\n");
+        output.push_str("  /** Splits a value into the single-bit flags it contains */\n");
+        output.push_str(&format!(
+            "  export function toFlagArray(value: number): {}[] {{\n",
+            self.name
+        ));
+        output.push_str(&format!(
+            "    return [{}].filter((flag) => hasFlag(value, flag));\n",
+            single_bits.join(", ")
+        ));
+        output.push_str("  }\n");
         output.push_str("}\n");
+
+        // JavaScript bitwise operators work on 32 bits, wider masks are only range checked
+        output.push_str(&format!(
+            "\nexport const {}Schema = z\n  .number()\n  .int()\n  .min(0)\n  .max({})",
+            self.name, mask
+        ));
+        if mask <= u32::MAX as i128 {
+            output.push_str(&format!(
+                "\n  .refine((value) => ((value & ~{}) >>> 0) === 0, {{\n    message: {},\n  }})",
+                mask,
+                ts_string(&format!("Unknown {} flag", self.name))
+            ));
+        }
+        output.push_str(";\n");
         output
     }
 }
 
-impl CSharpDto {
-    fn parse(content: &str) -> Result<Vec<Self>, &'static str> {
-        let mut dtos = Vec::new();
-
-        for dto_match in DTO_REGEX.captures_iter(content) {
-            let name = dto_match.name("name").unwrap().as_str().to_string();
-            let props_str = dto_match.name("props").unwrap().as_str();
-            
-            // Get all documentation comments before the DTO definition
-            let documentation = DOC_COMMENT_REGEX
-                .captures_iter(&content[..dto_match.get(0).unwrap().start()])
-                .map(|cap| cap[1].trim().to_string())
-                .collect::<Vec<_>>()
-                .join("\n");
-
-            let documentation = if documentation.is_empty() {
-                None
-            } else {
-                Some(documentation)
-            };
+/// TypeScript has no cast syntax; enum member references work without one
+fn without_casts(expr: &Expr) -> Expr {
+    match expr {
+        Expr::Cast(_, operand) => without_casts(operand),
+        Expr::Unary(op, operand) => Expr::Unary(op.clone(), Box::new(without_casts(operand))),
+        Expr::Binary(left, op, right) => Expr::Binary(
+            Box::new(without_casts(left)),
+            op.clone(),
+            Box::new(without_casts(right)),
+        ),
+        other => other.clone(),
+    }
+}
 
-            let mut properties = Vec::new();
-
-            // Process properties with their documentation
-            for prop in props_str.split(',') {
-                if let Some(cap) = PROPERTY_REGEX.captures(prop.trim()) {
-                    let type_str = cap.name("type").unwrap().as_str().trim();
-                    let name = cap.name("name").unwrap().as_str().trim().to_string();
-                    let type_name = CSharpType::from_string(type_str);
-
-                    let prop_docs = DOC_COMMENT_REGEX
-                        .captures_iter(prop)
-                        .map(|cap| cap[1].trim().to_string())
-                        .collect::<Vec<_>>()
-                        .join("\n");
-
-                    properties.push(DtoProperty {
-                        name,
-                        type_name,
-                        validations: Vec::new(),
-                        documentation: if prop_docs.is_empty() { None } else { Some(prop_docs) },
-                    });
+/// TypeScript literal for a C# constant: strings, numbers, booleans, known
+/// enum members and empty or literal collections
+fn default_literal(expr: &Expr, type_name: &CSharpType, types: &TypeRegistry) -> Option<String> {
+    let element_type = match type_name {
+        CSharpType::Array(element) | CSharpType::Set(element) => Some(element.as_ref()),
+        _ => None,
+    };
+    match (expr, type_name) {
+        (expr, CSharpType::Nullable(inner)) if *expr != Expr::Null => {
+            default_literal(expr, inner, types)
+        }
+        (Expr::Null, CSharpType::Nullable(_)) => Some("null".to_string()),
+        (Expr::String(text) | Expr::Char(text), _) => Some(ts_string(text)),
+        (Expr::Number(number), CSharpType::BigInteger { .. }) => {
+            Some(format!("{}n", numeric_literal(number)))
+        }
+        (Expr::Number(number), CSharpType::NumericString { .. }) => {
+            Some(ts_string(&numeric_literal(number)))
+        }
+        (Expr::Number(number), _) => Some(numeric_literal(number)),
+        (Expr::Unary(op, operand), _) if op == "-" => match operand.as_ref() {
+            Expr::Number(number) => match type_name {
+                CSharpType::BigInteger { .. } => Some(format!("-{}n", numeric_literal(number))),
+                CSharpType::NumericString { .. } => {
+                    Some(ts_string(&format!("-{}", numeric_literal(number))))
                 }
-            }
-
-            dtos.push(Self {
-                name,
-                properties,
-                documentation,
-            });
+                _ => Some(format!("-{}", numeric_literal(number))),
+            },
+            _ => None,
+        },
+        (Expr::Bool(value), _) => Some(value.to_string()),
+        (Expr::Name(name), _) if name == "string.Empty" || name == "String.Empty" => {
+            Some("''".to_string())
         }
+        (Expr::Name(name), _) => types.member_literal(name, type_name),
+        (Expr::Cast(_, operand), _) => default_literal(operand, type_name, types),
+        (Expr::New(_, arguments), CSharpType::Array(_) | CSharpType::Set(_))
+            if arguments.is_empty() =>
+        {
+            Some("[]".to_string())
+        }
+        (Expr::New(_, arguments), CSharpType::Dictionary(..)) if arguments.is_empty() => {
+            Some("{}".to_string())
+        }
+        (Expr::Collection(elements), CSharpType::Array(_) | CSharpType::Set(_)) => {
+            let elements = elements
+                .iter()
+                .map(|e| default_literal(e, element_type?, types))
+                .collect::<Option<Vec<_>>>()?;
+            Some(format!("[{}]", elements.join(", ")))
+        }
+        _ => None,
+    }
+}
+
+/// Drops digit separators and the `m`, `d`, `f`, `u` and `l` suffixes C#
+/// numbers may carry
+fn numeric_literal(number: &str) -> String {
+    let number = number.replace('_', "");
+    let is_hex = number.starts_with("0x") || number.starts_with("0X");
+    let suffixes: &[char] = if is_hex {
+        &['u', 'U', 'l', 'L']
+    } else {
+        &['m', 'M', 'd', 'D', 'f', 'F', 'u', 'U', 'l', 'L']
+    };
+    number.trim_end_matches(suffixes).to_string()
+}
+
+/// Configured mapping for a type reference, with the mapping's type parameter
+/// names. Keys may be qualified, in which case the reference may leave out
+/// any leading namespaces.
+fn find_type_mapping<'a>(
+    mappings: &'a BTreeMap<String, TypeMapping>,
+    qualifier: &[String],
+    name: &str,
+    arity: usize,
+) -> Option<(&'a TypeMapping, Vec<String>)> {
+    mappings.iter().find_map(|(key, mapping)| {
+        let (path, parameters) = match key.split_once('<') {
+            Some((path, rest)) => (
+                path.trim(),
+                rest.trim_end()
+                    .trim_end_matches('>')
+                    .split(',')
+                    .map(|p| p.trim().to_string())
+                    .collect(),
+            ),
+            None => (key.trim(), Vec::new()),
+        };
+        let segments: Vec<&str> = path.split('.').collect();
+        let (key_name, key_qualifier) = segments.split_last()?;
+        // References to project types are resolved to their full name, which
+        // may say more than the key
+        let qualifier: Vec<&str> = qualifier
+            .iter()
+            .map(String::as_str)
+            .filter(|segment| *segment != "global")
+            .collect();
+        let matches = *key_name == name
+            && parameters.len() == arity
+            && (key_qualifier.ends_with(&qualifier) || qualifier.ends_with(key_qualifier));
+        matches.then_some((mapping, parameters))
+    })
+}
+
+/// Replaces `{T}` placeholders in a type mapping template with the rendered
+/// type arguments
+fn fill_type_parameters(
+    template: &str,
+    arguments: &[(String, CSharpType)],
+    render: impl Fn(&CSharpType) -> String,
+) -> String {
+    arguments
+        .iter()
+        .fold(template.to_string(), |text, (name, argument)| {
+            text.replace(&format!("{{{}}}", name), &render(argument))
+        })
+}
+
+/// Sequence types that serialize as plain JSON arrays
+fn is_list_type(name: &str) -> bool {
+    matches!(
+        name,
+        "List"
+            | "IEnumerable"
+            | "ICollection"
+            | "IList"
+            | "IReadOnlyList"
+            | "IReadOnlyCollection"
+            | "Collection"
+            | "ReadOnlyCollection"
+            | "ImmutableArray"
+            | "ImmutableList"
+            | "IImmutableList"
+            | "LinkedList"
+            | "Queue"
+            | "Stack"
+    )
+}
+
+/// Set types, which serialize as arrays without duplicates
+fn is_set_type(name: &str) -> bool {
+    matches!(
+        name,
+        "HashSet" | "ISet" | "IReadOnlySet" | "SortedSet" | "ImmutableHashSet" | "IImmutableSet"
+    )
+}
+
+/// Dictionary types, which serialize as JSON objects
+fn is_dictionary_type(name: &str) -> bool {
+    matches!(
+        name,
+        "Dictionary"
+            | "IDictionary"
+            | "IReadOnlyDictionary"
+            | "SortedDictionary"
+            | "ReadOnlyDictionary"
+            | "ConcurrentDictionary"
+            | "ImmutableDictionary"
+            | "IImmutableDictionary"
+    )
+}
 
-        Ok(dtos)
+/// Whole numbers within the range of the C# type; 64-bit values are limited
+/// to what a JavaScript number holds exactly
+fn integer_schema(bits: u32, signed: bool) -> String {
+    match (bits, signed) {
+        (32, true) => "z.number().int()".to_string(),
+        (64, true) => "z.number().int().safe()".to_string(),
+        (64, false) => "z.number().int().nonnegative().safe()".to_string(),
+        (bits, true) => format!(
+            "z.number().int().min({}).max({})",
+            -(1i64 << (bits - 1)),
+            (1i64 << (bits - 1)) - 1
+        ),
+        (bits, false) => format!("z.number().int().min(0).max({})", (1u64 << bits) - 1),
     }
+}
 
-    fn is_update_dto(&self) -> bool {
-        self.name.starts_with("Update")
+/// Bit width and signedness of the C# integral types
+fn integral_type_layout(type_name: &str) -> Option<(u32, bool)> {
+    match type_name.rsplit('.').next()? {
+        "sbyte" | "SByte" => Some((8, true)),
+        "byte" | "Byte" => Some((8, false)),
+        "short" | "Int16" => Some((16, true)),
+        "ushort" | "UInt16" => Some((16, false)),
+        "int" | "Int32" => Some((32, true)),
+        "uint" | "UInt32" => Some((32, false)),
+        "long" | "Int64" => Some((64, true)),
+        "ulong" | "UInt64" => Some((64, false)),
+        "nint" | "IntPtr" => Some((64, true)),
+        "nuint" | "UIntPtr" => Some((64, false)),
+        _ => None,
     }
+}
 
-    fn to_zod_schema(&self, config: &Config) -> String {
-        let mut output = String::new();
-        output.push_str(&generate_file_header(config, "Zod Schema"));
+/// Wraps a value the way C# constant conversion would, so `~0` in a `uint`
+/// enum is 4294967295 rather than -1
+fn wrap_to_integral_type(value: i128, type_name: &str) -> i128 {
+    let Some((bits, signed)) = integral_type_layout(type_name) else {
+        return value;
+    };
+    let modulus = 1i128 << bits;
+    let wrapped = value.rem_euclid(modulus);
+    if signed && wrapped >= modulus / 2 {
+        wrapped - modulus
+    } else {
+        wrapped
+    }
+}
 
-        // Add imports
-        output.push_str("import { z } from 'zod';\n");
-        
-        // i18n import if localized
-        if config.localized {
-            output.push_str(&format!("import {{ useI18n }} from '{}';\n", config.i18n_library));
+impl DtoProperty {
+    /// Schema for the property as a field of its DTO
+    fn to_zod_schema(&self, types: &TypeRegistry, localized: bool, is_update_dto: bool) -> String {
+        let mut schema = self.validated_schema(types, localized);
+        // A default makes the field optional on input, so it replaces the
+        // presence modifier; update DTOs may leave any field out
+        match self.default_chain(types) {
+            Some(default) => schema.push_str(&default),
+            None if is_update_dto => schema.push_str(".optional()"),
+            None => {}
         }
-        
-        // Additional imports
-        for import in &config.additional_imports {
-            output.push_str(&format!("import {} from '{}';\n", import.name, import.path));
+        schema
+    }
+
+    /// Schema of the value with its validations. These are string and number
+    /// checks, which `.nullable()` would hide, so it comes after them
+    fn validated_schema(&self, types: &TypeRegistry, localized: bool) -> String {
+        let (value_type, nullable) = match self.validated_type(types) {
+            CSharpType::Nullable(inner) => (*inner, true),
+            other => (other, false),
+        };
+        let mut schema = value_type.to_zod_schema(types, localized);
+        schema.push_str(&self.validation_chain(localized));
+        if nullable {
+            schema.push_str(".nullable()");
         }
-        
-        output.push_str("\n");
+        schema
+    }
 
-        let is_update = self.is_update_dto();
+    fn validation_chain(&self, localized: bool) -> String {
+        // Required is already handled by the type's presence modifier
+        self.validations
+            .iter()
+            .filter(|validation| validation.rule_type != "Required")
+            .filter_map(|validation| validation.to_zod_validation(&self.name, localized))
+            .collect()
+    }
 
-        // Add documentation if available
-        if let Some(doc) = &self.documentation {
-            output.push_str("/**\n");
-            output.push_str(&format!(" * {}\n", doc));
-            output.push_str(" */\n");
+    fn from_parameter(
+        param: &Parameter,
+        capitalize: bool,
+        documentation: Option<String>,
+        config: &Config,
+    ) -> Self {
+        let mut name = param.name.clone();
+        if capitalize {
+            if let Some(first) = name.get(..1) {
+                name = first.to_uppercase() + &name[1..];
+            }
         }
 
-        if config.localized {
-            output.push_str(&format!("export const {}Schema = () => {{\n", self.name));
-            output.push_str("  const { t } = useI18n();\n");
-            output.push_str("  return z.object({\n");
-        } else {
-            output.push_str(&format!("export const {}Schema = z.object({{\n", self.name));
+        Self {
+            name,
+            type_name: CSharpType::from_mapped_syntax(&param.type_name, &config.type_mappings),
+            validations: param
+                .attributes
+                .iter()
+                .filter_map(ValidationRule::from_attribute)
+                .collect(),
+            default_value: param.default_value.clone(),
+            documentation,
+            oblivious: param.nullable_annotations == Some(false),
         }
+    }
 
-        // Generate properties
-        for prop in &self.properties {
-            if let Some(doc) = &prop.documentation {
-                output.push_str(&format!("    /** {} */\n", doc));
-            }
+    fn from_property(
+        property: &PropertyDecl,
+        documentation: Option<String>,
+        config: &Config,
+    ) -> Self {
+        Self {
+            name: property.name.clone(),
+            type_name: CSharpType::from_mapped_syntax(&property.type_name, &config.type_mappings),
+            validations: property
+                .attributes
+                .iter()
+                .filter_map(ValidationRule::from_attribute)
+                .collect(),
+            default_value: property.initializer.clone(),
+            documentation,
+            oblivious: property.nullable_annotations == Some(false),
+        }
+    }
 
-            let mut schema_line = format!(
-                "    {}: {}",
-                prop.name,
-                prop.type_name.to_zod_type(config.localized, is_update)
-            );
+    /// `.default(...)` for values with a TypeScript literal equivalent
+    fn default_chain(&self, types: &TypeRegistry) -> Option<String> {
+        let value = default_literal(
+            self.default_value.as_ref()?,
+            &self.validated_type(types),
+            types,
+        )?;
+        Some(format!(".default({})", value))
+    }
 
-            // Add additional validations (but don't add .required() since it's already handled)
-            for validation in &prop.validations {
-                if validation.rule_type != "Required" {
-                    if let Some(validation_code) =
-                        validation.to_zod_validation(&prop.name, config.localized)
-                    {
-                        schema_line.push_str(&validation_code);
-                    }
-                }
+    /// Type as it is validated: `[Required]` rules out null, and reference
+    /// types declared without nullable annotations admit it
+    fn validated_type(&self, types: &TypeRegistry) -> CSharpType {
+        let required = self
+            .validations
+            .iter()
+            .any(|validation| validation.rule_type == "Required");
+        match &self.type_name {
+            CSharpType::Nullable(inner) if required => inner.as_ref().clone(),
+            CSharpType::Nullable(_) => self.type_name.clone(),
+            other if self.oblivious && !required && other.is_reference_type(types) => {
+                CSharpType::Nullable(Box::new(other.clone()))
             }
+            other => other.clone(),
+        }
+    }
+}
 
-            output.push_str(&schema_line);
-            output.push_str(",\n");
+impl TypeRegistry {
+    fn new(units: &[CompilationUnit], docs: &DocResolver, config: &Config) -> Self {
+        let mut dtos = Vec::new();
+        for unit in units {
+            dtos.extend(CSharpDto::parse(unit, docs, config));
+            if config.interface_schemas {
+                dtos.extend(
+                    CSharpInterface::parse(unit, docs, config)
+                        .iter()
+                        .map(CSharpInterface::to_dto),
+                );
+            }
         }
+        let schemas: HashSet<String> = dtos.iter().map(|dto| dto.full_name.clone()).collect();
+        let structs = dtos
+            .iter()
+            .filter(|dto| dto.is_struct)
+            .map(|dto| dto.full_name.clone())
+            .collect();
 
-        if config.localized {
-            output.push_str("  });\n};\n");
-        } else {
-            output.push_str("});\n");
+        // Schemas refer to each other through their bases and properties
+        let graph: HashMap<&str, Vec<&str>> = dtos
+            .iter()
+            .map(|dto| {
+                let mut references: Vec<&str> = dto.bases().collect();
+                for property in &dto.properties {
+                    property.type_name.collect_references(&mut references, true);
+                }
+                references.retain(|name| schemas.contains(*name));
+                (dto.full_name.as_str(), references)
+            })
+            .collect();
+        let cycles: HashMap<String, usize> = reference_cycles(&graph)
+            .into_iter()
+            .map(|(name, cycle)| (name.to_string(), cycle))
+            .collect();
+        let object_schemas = dtos
+            .iter()
+            .filter(|dto| dto.type_parameters.is_empty() && cycles.contains_key(&dto.full_name))
+            .filter(|dto| {
+                dtos.iter()
+                    .any(|other| other.bases().any(|base| base == dto.full_name))
+            })
+            .map(|dto| dto.full_name.clone())
+            .collect();
+
+        Self {
+            enums: units
+                .iter()
+                .flat_map(|unit| CSharpEnum::parse(unit, docs, config))
+                .map(|e| (e.full_name.clone(), e))
+                .collect(),
+            schemas,
+            structs,
+            cycles,
+            object_schemas,
+            enum_style: config.enum_style,
         }
+    }
 
-        output.push_str(&format!(
-            "\nexport type {} = z.infer<typeof {}Schema>;\n",
-            self.name, self.name
-        ));
+    /// Whether `from` and `to` are part of the same reference cycle, which
+    /// includes a type referring to itself
+    fn in_same_cycle(&self, from: &str, to: &str) -> bool {
+        self.cycles
+            .get(from)
+            .is_some_and(|cycle| self.cycles.get(to) == Some(cycle))
+    }
 
-        output
+    fn classify(&self, name: &str) -> TypeClass<'_> {
+        match self.enums.get(name) {
+            Some(csharp_enum) => TypeClass::Enum(csharp_enum),
+            None if self.schemas.contains(name) => TypeClass::Schema,
+            None => TypeClass::Unknown,
+        }
     }
-}
 
-pub fn process_directory(
-    processor: &mut FileProcessor,
-    dir_path: &Path,
-    input_root: &Path,
-    output_root: &Path,
-    config: &Config,
-) -> std::io::Result<()> {
-    if dir_path.is_dir() {
-        for entry in fs::read_dir(dir_path)? {
-            let entry = entry?;
-            let path = entry.path();
-
-            if path.is_dir() {
-                process_directory(processor, &path, input_root, output_root, config)?;
-            } else if config.is_valid_extension(&path.to_path_buf())
-                && !config.should_ignore(&path.to_path_buf())
+    /// A member reference such as `Status.Active`, as the generated enum
+    /// represents its value. Properties typed as the enum itself refer to the
+    /// member, since TypeScript enums don't accept their raw values; flags
+    /// schemas are plain numbers.
+    fn member_literal(&self, reference: &str, property_type: &CSharpType) -> Option<String> {
+        let (owner, member) = reference.rsplit_once('.')?;
+        // The owner is written as in the source, so the property's own enum
+        // is preferred over other enums sharing its name
+        let written = format!(".{}", owner);
+        let matches = |full_name: &str| full_name == owner || full_name.ends_with(&written);
+        let csharp_enum = match property_type {
+            CSharpType::Custom(name) if matches(name) => self.enums.get(name)?,
+            _ => {
+                let mut candidates = self.enums.values().filter(|e| matches(&e.full_name));
+                match (candidates.next(), candidates.next()) {
+                    (Some(csharp_enum), None) => csharp_enum,
+                    _ => return None,
+                }
+            }
+        };
+        let value = csharp_enum.values.iter().find(|v| v.name == member)?;
+        let style = csharp_enum.style(self.enum_style);
+        match property_type {
+            CSharpType::Custom(name)
+                if *name == csharp_enum.full_name
+                    && style != EnumStyle::Union
+                    && !csharp_enum.is_flags =>
             {
-                processor.process_file(&path, input_root, output_root, config)?;
+                return Some(format!("{}.{}", csharp_enum.name, member));
             }
+            _ => {}
+        }
+        match style {
+            EnumStyle::String | EnumStyle::Union => Some(ts_string(
+                value.display_name.as_ref().unwrap_or(&value.name),
+            )),
+            EnumStyle::Numeric => value.value.map(|n| n.to_string()),
         }
-    } else if config.is_valid_extension(&dir_path.to_path_buf())
-        && !config.should_ignore(&dir_path.to_path_buf())
-    {
-        processor.process_file(dir_path, input_root, output_root, config)?;
     }
+}
 
-    Ok(())
+/// Strongly connected components of the schema reference graph that form a
+/// cycle, as the index of each member's component (Tarjan's algorithm)
+fn reference_cycles<'a>(graph: &HashMap<&'a str, Vec<&'a str>>) -> HashMap<&'a str, usize> {
+    struct Search<'a, 'g> {
+        graph: &'g HashMap<&'a str, Vec<&'a str>>,
+        index: HashMap<&'a str, usize>,
+        low_link: HashMap<&'a str, usize>,
+        stack: Vec<&'a str>,
+        cycles: HashMap<&'a str, usize>,
+        components: usize,
+    }
+
+    impl<'a> Search<'a, '_> {
+        fn visit(&mut self, node: &'a str) {
+            let index = self.index.len();
+            self.index.insert(node, index);
+            self.low_link.insert(node, index);
+            self.stack.push(node);
+
+            for &next in self.graph.get(node).into_iter().flatten() {
+                if !self.index.contains_key(next) {
+                    self.visit(next);
+                    let low = self.low_link[node].min(self.low_link[next]);
+                    self.low_link.insert(node, low);
+                } else if self.stack.contains(&next) {
+                    let low = self.low_link[node].min(self.index[next]);
+                    self.low_link.insert(node, low);
+                }
+            }
+
+            if self.low_link[node] == index {
+                let position = self.stack.iter().rposition(|n| *n == node).unwrap();
+                let component = self.stack.split_off(position);
+                let refers_to_itself = self.graph[node].contains(&node);
+                if component.len() > 1 || refers_to_itself {
+                    for member in component {
+                        self.cycles.insert(member, self.components);
+                    }
+                }
+                self.components += 1;
+            }
+        }
+    }
+
+    let mut search = Search {
+        graph,
+        index: HashMap::new(),
+        low_link: HashMap::new(),
+        stack: Vec::new(),
+        cycles: HashMap::new(),
+        components: 0,
+    };
+    let mut nodes: Vec<&str> = graph.keys().copied().collect();
+    nodes.sort_unstable();
+    for node in nodes {
+        if !search.index.contains_key(node) {
+            search.visit(node);
+        }
+    }
+    search.cycles
 }
 
-pub fn process_single_file(
-    processor: &mut FileProcessor,
-    input_path: &Path,
-    output_dir: &Path,
-    config: &Config,
-) -> std::io::Result<()> {
-    let input_root = config
-        .input_dir
-        .as_ref()
-        .map(|p| p.as_path())
-        .unwrap_or_else(|| input_path.parent().unwrap_or(Path::new("")));
+impl SymbolTable {
+    fn new(output_root: &Path) -> Self {
+        Self {
+            symbols: HashMap::new(),
+            output_root: output_root.to_path_buf(),
+        }
+    }
 
-    if input_path.is_dir() {
-        process_directory(processor, input_path, input_root, output_dir, config)
-    } else {
-        processor.process_file(input_path, input_root, output_dir, config)
+    /// Records the types `unit` generates, given the base of its output files
+    fn register(
+        &mut self,
+        unit: &CompilationUnit,
+        output_base: &Path,
+        docs: &DocResolver,
+        config: &Config,
+    ) {
+        let enum_module = output_base.to_path_buf();
+        let schema_module = with_suffix(output_base, ".schema");
+
+        let mut add = |name: &str, kind, module: &PathBuf| {
+            self.symbols.insert(
+                name.to_string(),
+                Symbol {
+                    kind,
+                    module: module.clone(),
+                },
+            );
+        };
+        for csharp_enum in CSharpEnum::parse(unit, docs, config) {
+            let kind = match csharp_enum.style(config.enum_style) {
+                _ if csharp_enum.is_flags => SymbolKind::FlagsEnum,
+                EnumStyle::Union => SymbolKind::UnionEnum,
+                EnumStyle::String | EnumStyle::Numeric => SymbolKind::Enum,
+            };
+            add(&csharp_enum.full_name, kind, &enum_module);
+        }
+        for dto in CSharpDto::parse(unit, docs, config) {
+            add(&dto.full_name, SymbolKind::Schema, &schema_module);
+        }
+        for interface in CSharpInterface::parse(unit, docs, config) {
+            let kind = if config.interface_schemas {
+                SymbolKind::Schema
+            } else {
+                SymbolKind::Interface
+            };
+            add(&interface.full_name, kind, &schema_module);
+        }
+    }
+
+    /// Module specifier and symbol to import for a reference to `name` from a
+    /// file generated into `from_dir`
+    fn import(
+        &self,
+        name: &str,
+        usage: SymbolUsage,
+        from_dir: &Path,
+        config: &Config,
+    ) -> Option<(String, String)> {
+        let symbol = self.symbols.get(name)?;
+        let name = simple_name(name);
+        let imported = match (usage, symbol.kind) {
+            (SymbolUsage::Type, _) => format!("type {}", name),
+            (SymbolUsage::Schema, SymbolKind::Schema | SymbolKind::FlagsEnum) => {
+                format!("{}Schema", name)
+            }
+            (SymbolUsage::Schema, SymbolKind::Enum) => name.to_string(),
+            (SymbolUsage::Schema, SymbolKind::Interface | SymbolKind::UnionEnum) => return None,
+        };
+        Some((
+            self.module_specifier(&symbol.module, from_dir, config),
+            imported,
+        ))
+    }
+
+    fn module_specifier(&self, module: &Path, from_dir: &Path, config: &Config) -> String {
+        if let Some(alias) = &config.import_alias {
+            let relative = module.strip_prefix(&self.output_root).unwrap_or(module);
+            return format!(
+                "{}/{}",
+                alias.trim_end_matches('/'),
+                path_segments(relative).join("/")
+            );
+        }
+
+        let from = path_segments(from_dir);
+        let to = path_segments(module);
+        let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+        let mut segments: Vec<String> = vec!["..".to_string(); from.len() - common];
+        if segments.is_empty() {
+            segments.push(".".to_string());
+        }
+        segments.extend(to[common..].iter().cloned());
+        segments.join("/")
+    }
+}
+
+/// `base` with `suffix` appended to its file name, which may contain dots
+fn with_suffix(base: &Path, suffix: &str) -> PathBuf {
+    let mut path = base.as_os_str().to_owned();
+    path.push(suffix);
+    PathBuf::from(path)
+}
+
+/// Normal components of a path, for building `/`-separated module specifiers
+fn path_segments(path: &Path) -> Vec<String> {
+    path.components()
+        .filter_map(|component| match component {
+            std::path::Component::Normal(segment) => Some(segment.to_string_lossy().into_owned()),
+            _ => None,
+        })
+        .collect()
+}
+
+impl CSharpConstants {
+    fn parse(unit: &CompilationUnit, docs: &DocResolver, config: &Config) -> Vec<Self> {
+        let types = unit.types();
+        let is_constant_class = |decl: &TypeDecl| {
+            decl.kind == TypeKind::Class
+                && decl.has_modifier("static")
+                && decl.has_modifier("public")
+                && !is_dropped(&decl.attributes, config)
+        };
+
+        // Nested static classes are emitted inside their parent
+        let nested: Vec<&TypeDecl> = types
+            .iter()
+            .filter(|decl| is_constant_class(decl))
+            .flat_map(|decl| &decl.members)
+            .filter_map(|member| match member {
+                MemberDecl::Type(nested) => Some(nested),
+                _ => None,
+            })
+            .collect();
+
+        types
+            .into_iter()
+            .filter(|decl| is_constant_class(decl))
+            .filter(|decl| !nested.iter().any(|n| std::ptr::eq(*n, *decl)))
+            .filter_map(|decl| Self::from_declaration(decl, docs, config))
+            .collect()
+    }
+
+    fn from_declaration(decl: &TypeDecl, docs: &DocResolver, config: &Config) -> Option<Self> {
+        // Later constants may be built from earlier ones
+        let mut known: HashMap<String, ConstantValue> = HashMap::new();
+        let mut fields = Vec::new();
+        let mut nested = Vec::new();
+        for member in &decl.members {
+            match member {
+                MemberDecl::Field(field) => {
+                    let is_constant = field.has_modifier("const")
+                        || (field.has_modifier("static") && field.has_modifier("readonly"));
+                    if !field.has_modifier("public")
+                        || !is_constant
+                        || !CSharpType::from_syntax(&field.type_name).is_primitive()
+                    {
+                        continue;
+                    }
+                    let Some(value) = field
+                        .initializer
+                        .as_ref()
+                        .and_then(|expr| evaluate_constant(expr, &decl.name, &known))
+                    else {
+                        continue;
+                    };
+                    known.insert(field.name.clone(), value.clone());
+                    if is_dropped(&field.attributes, config) {
+                        continue;
+                    }
+                    fields.push(ConstantField {
+                        name: field.name.clone(),
+                        value: value.to_typescript(),
+                        documentation: jsdoc_text(
+                            docs.member_doc(decl, &field.name),
+                            &field.attributes,
+                        ),
+                    });
+                }
+                MemberDecl::Type(inner)
+                    if inner.kind == TypeKind::Class
+                        && inner.has_modifier("static")
+                        && inner.has_modifier("public")
+                        && !is_dropped(&inner.attributes, config) =>
+                {
+                    nested.extend(Self::from_declaration(inner, docs, config));
+                }
+                _ => {}
+            }
+        }
+
+        if fields.is_empty() && nested.is_empty() {
+            return None;
+        }
+        Some(Self {
+            name: decl.name.clone(),
+            fields,
+            nested,
+            documentation: jsdoc_text(docs.type_doc(decl), &decl.attributes),
+        })
+    }
+
+    fn to_typescript(&self) -> String {
+        let mut output = String::new();
+        if let Some(doc) = &self.documentation {
+            output.push_str(&jsdoc(doc, "", false));
+        }
+        output.push_str(&format!("export const {} = {{\n", self.name));
+        self.write_members(&mut output, "  ");
+        output.push_str("} as const;\n");
+        self.write_union_types(&mut output, &self.name, &self.name);
+        output
+    }
+
+    fn write_members(&self, output: &mut String, indent: &str) {
+        for field in &self.fields {
+            if let Some(doc) = &field.documentation {
+                output.push_str(&jsdoc(doc, indent, true));
+            }
+            output.push_str(&format!("{}{}: {},\n", indent, field.name, field.value));
+        }
+        for nested in &self.nested {
+            if let Some(doc) = &nested.documentation {
+                output.push_str(&jsdoc(doc, indent, true));
+            }
+            output.push_str(&format!("{}{}: {{\n", indent, nested.name));
+            nested.write_members(output, &format!("{}  ", indent));
+            output.push_str(&format!("{}}},\n", indent));
+        }
+    }
+
+    /// Union of the values a class holds directly, named after its path:
+    /// `Claims.Types` gets `ClaimsTypes`
+    fn write_union_types(&self, output: &mut String, path: &str, type_name: &str) {
+        if !self.fields.is_empty() {
+            let keys = if self.nested.is_empty() {
+                format!("keyof typeof {}", path)
+            } else {
+                self.fields
+                    .iter()
+                    .map(|f| ts_string(&f.name))
+                    .collect::<Vec<_>>()
+                    .join(" | ")
+            };
+            output.push_str(&format!(
+                "\nexport type {} = (typeof {})[{}];\n",
+                type_name, path, keys
+            ));
+        }
+        for nested in &self.nested {
+            nested.write_union_types(
+                output,
+                &format!("{}.{}", path, nested.name),
+                &format!("{}{}", type_name, nested.name),
+            );
+        }
+    }
+}
+
+impl ConstantValue {
+    /// Text a value contributes to a string concatenation
+    fn as_text(&self) -> String {
+        match self {
+            ConstantValue::String(text) => text.clone(),
+            ConstantValue::Integer(number) => number.to_string(),
+            ConstantValue::Literal(literal) => literal.clone(),
+        }
+    }
+
+    fn to_typescript(&self) -> String {
+        match self {
+            ConstantValue::String(text) => ts_string(text),
+            ConstantValue::Integer(number) => number.to_string(),
+            ConstantValue::Literal(literal) => literal.clone(),
+        }
+    }
+}
+
+/// Folds literals, references to constants declared earlier in the same
+/// class, integer arithmetic and string concatenation
+fn evaluate_constant(
+    expr: &Expr,
+    class_name: &str,
+    known: &HashMap<String, ConstantValue>,
+) -> Option<ConstantValue> {
+    let own_prefix = format!("{}.", class_name);
+    let lookup = |name: &str| known.get(name.strip_prefix(&own_prefix).unwrap_or(name));
+    match expr {
+        Expr::String(text) | Expr::Char(text) => Some(ConstantValue::String(text.clone())),
+        Expr::Bool(value) => Some(ConstantValue::Literal(value.to_string())),
+        Expr::Name(name) => lookup(name).cloned(),
+        Expr::Binary(left, op, right) if op == "+" => {
+            let left = evaluate_constant(left, class_name, known)?;
+            let right = evaluate_constant(right, class_name, known)?;
+            match (left, right) {
+                (ConstantValue::Integer(l), ConstantValue::Integer(r)) => {
+                    l.checked_add(r).map(ConstantValue::Integer)
+                }
+                (ConstantValue::String(l), r) => Some(ConstantValue::String(l + &r.as_text())),
+                (l, ConstantValue::String(r)) => Some(ConstantValue::String(l.as_text() + &r)),
+                _ => None,
+            }
+        }
+        expr => {
+            let integer = expr.evaluate(&|name| match lookup(name) {
+                Some(ConstantValue::Integer(value)) => Some(*value),
+                _ => None,
+            });
+            match (integer, expr) {
+                (Some(value), _) => Some(ConstantValue::Integer(value)),
+                (None, Expr::Number(number)) => {
+                    Some(ConstantValue::Literal(numeric_literal(number)))
+                }
+                (None, Expr::Unary(op, operand)) if op == "-" => match operand.as_ref() {
+                    Expr::Number(number) => Some(ConstantValue::Literal(format!(
+                        "-{}",
+                        numeric_literal(number)
+                    ))),
+                    _ => None,
+                },
+                _ => None,
+            }
+        }
+    }
+}
+
+impl CSharpInterface {
+    fn parse(unit: &CompilationUnit, docs: &DocResolver, config: &Config) -> Vec<Self> {
+        unit.types()
+            .into_iter()
+            .filter(|decl| decl.kind == TypeKind::Interface && decl.has_modifier("public"))
+            .filter(|decl| !is_dropped(&decl.attributes, config))
+            .map(|decl| Self::from_declaration(decl, docs, config))
+            .collect()
+    }
+
+    fn from_declaration(decl: &TypeDecl, docs: &DocResolver, config: &Config) -> Self {
+        // Interface members are implicitly public, so there are no modifiers
+        // to check beyond `static` abstract members
+        let properties = decl
+            .members
+            .iter()
+            .filter_map(|member| match member {
+                MemberDecl::Property(property) => Some(property),
+                _ => None,
+            })
+            .filter(|property| !property.has_modifier("static") && property.is_readable())
+            .filter(|property| !is_dropped(&property.attributes, config))
+            .map(|property| {
+                let documentation =
+                    jsdoc_text(docs.member_doc(decl, &property.name), &property.attributes);
+                DtoProperty::from_property(property, documentation, config)
+            })
+            .map(|prop| {
+                let (integers, decimals) = config.precision.for_property(&decl.name, &prop.name);
+                DtoProperty {
+                    type_name: prop
+                        .type_name
+                        .bind_type_parameters(&decl.type_parameters)
+                        .with_precision(integers, decimals),
+                    ..prop
+                }
+            })
+            .collect();
+
+        Self {
+            name: decl.name.clone(),
+            full_name: decl.full_name.clone(),
+            type_parameters: decl.type_parameters.clone(),
+            base_interfaces: decl
+                .base_types
+                .iter()
+                .map(|base| {
+                    CSharpType::from_syntax(base).bind_type_parameters(&decl.type_parameters)
+                })
+                .collect(),
+            properties,
+            documentation: jsdoc_text(docs.type_doc(decl), &decl.attributes),
+        }
+    }
+
+    fn bases(&self) -> impl Iterator<Item = &str> {
+        self.base_interfaces
+            .iter()
+            .filter_map(|base| base.schema_name())
+    }
+
+    fn to_typescript(&self, types: &TypeRegistry) -> String {
+        let mut output = String::new();
+
+        if let Some(doc) = &self.documentation {
+            output.push_str(&jsdoc(doc, "", false));
+        }
+
+        output.push_str(&format!("export interface {}", self.name));
+        if !self.type_parameters.is_empty() {
+            output.push_str(&format!("<{}>", self.type_parameters.join(", ")));
+        }
+        if !self.base_interfaces.is_empty() {
+            output.push_str(&format!(
+                " extends {}",
+                self.base_interfaces
+                    .iter()
+                    .map(|base| base.to_typescript_type())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+        output.push_str(" {\n");
+
+        for prop in &self.properties {
+            if let Some(doc) = &prop.documentation {
+                output.push_str(&jsdoc(doc, "  ", true));
+            }
+            output.push_str(&format!(
+                "  {}: {};\n",
+                prop.name,
+                prop.validated_type(types).to_typescript_type()
+            ));
+        }
+
+        output.push_str("}\n");
+        output
+    }
+
+    /// Schema validating objects of this interface, merging those of its bases
+    fn to_dto(&self) -> CSharpDto {
+        CSharpDto {
+            name: self.name.clone(),
+            full_name: self.full_name.clone(),
+            is_struct: false,
+            type_parameters: self.type_parameters.clone(),
+            base_type: None,
+            mixins: self.base_interfaces.clone(),
+            is_interface: true,
+            properties: self.properties.clone(),
+            documentation: None,
+        }
+    }
+}
+
+impl CSharpDto {
+    fn parse(unit: &CompilationUnit, docs: &DocResolver, config: &Config) -> Vec<Self> {
+        unit.types()
+            .into_iter()
+            .filter(|decl| {
+                matches!(
+                    decl.kind,
+                    TypeKind::Class | TypeKind::Record | TypeKind::Struct | TypeKind::RecordStruct
+                )
+            })
+            .filter(|decl| decl.has_modifier("public") && !decl.has_modifier("static"))
+            .filter(|decl| !is_dropped(&decl.attributes, config))
+            .filter_map(|decl| Self::from_declaration(decl, docs, config))
+            .collect()
+    }
+
+    fn from_declaration(decl: &TypeDecl, docs: &DocResolver, config: &Config) -> Option<Self> {
+        let body_properties: Vec<&PropertyDecl> = decl
+            .members
+            .iter()
+            .filter_map(|member| match member {
+                MemberDecl::Property(property) => Some(property),
+                _ => None,
+            })
+            .filter(|property| property.is_public_instance() && property.is_readable())
+            .collect();
+
+        let is_struct = matches!(decl.kind, TypeKind::Struct | TypeKind::RecordStruct);
+        let member_doc = |name: &str, attributes: &[Attribute]| {
+            jsdoc_text(docs.member_doc(decl, name), attributes)
+        };
+
+        // Classes without a single public property are services, not DTOs,
+        // unless they are abstract bases other DTOs extend
+        if matches!(decl.kind, TypeKind::Class | TypeKind::Struct)
+            && body_properties.is_empty()
+            && decl.parameters.is_none()
+            && !decl.has_modifier("abstract")
+        {
+            return None;
+        }
+
+        // Positional parameters come first; a body property redeclaring one
+        // (`public string Name { get; } = name;`) takes its place
+        let mut properties = Vec::new();
+        let mut dropped = Vec::new();
+        for param in decl.parameters.iter().flatten() {
+            let redeclared = body_properties
+                .iter()
+                .find(|property| property.name.eq_ignore_ascii_case(&param.name));
+            let attributes = redeclared.map_or(&param.attributes, |p| &p.attributes);
+            if is_dropped(attributes, config) {
+                dropped.push(param.name.as_str());
+                continue;
+            }
+            properties.push(match redeclared {
+                Some(property) => DtoProperty::from_property(
+                    property,
+                    member_doc(&property.name, &property.attributes),
+                    config,
+                ),
+                None => DtoProperty::from_parameter(
+                    param,
+                    decl.kind == TypeKind::Class,
+                    member_doc(&param.name, &param.attributes),
+                    config,
+                ),
+            });
+        }
+        for property in body_properties {
+            let seen = properties
+                .iter()
+                .map(|p: &DtoProperty| p.name.as_str())
+                .chain(dropped.iter().copied())
+                .any(|name| name.eq_ignore_ascii_case(&property.name));
+            if !seen && !is_dropped(&property.attributes, config) {
+                properties.push(DtoProperty::from_property(
+                    property,
+                    member_doc(&property.name, &property.attributes),
+                    config,
+                ));
+            }
+        }
+
+        // Only a class or record can be the first base; interfaces follow it
+        // and are all a struct may list
+        let base_type = decl
+            .base_types
+            .first()
+            .filter(|_| !is_struct)
+            .map(|base| CSharpType::from_syntax(base).bind_type_parameters(&decl.type_parameters))
+            .filter(|base| {
+                base.schema_name()
+                    .is_some_and(|name| !is_interface_name(simple_name(name)))
+            });
+
+        let properties = properties
+            .into_iter()
+            .map(|prop| {
+                let (integers, decimals) = config.precision.for_property(&decl.name, &prop.name);
+                DtoProperty {
+                    type_name: prop
+                        .type_name
+                        .bind_type_parameters(&decl.type_parameters)
+                        .with_precision(integers, decimals),
+                    ..prop
+                }
+            })
+            .collect();
+
+        Some(Self {
+            name: decl.name.clone(),
+            full_name: decl.full_name.clone(),
+            is_struct,
+            type_parameters: decl.type_parameters.clone(),
+            base_type,
+            mixins: Vec::new(),
+            is_interface: false,
+            properties,
+            // `<param>` tags went to the properties they describe
+            documentation: jsdoc_text(
+                docs.type_doc(decl).map(|doc| XmlDoc {
+                    params: Vec::new(),
+                    ..doc
+                }),
+                &decl.attributes,
+            ),
+        })
+    }
+
+    /// Schema of the wrapped value itself, optionally branded with the struct name
+    fn to_wrapper_schema(
+        &self,
+        property: &DtoProperty,
+        types: &TypeRegistry,
+        config: &Config,
+    ) -> String {
+        // The struct stands for its value, so a disabled nullable context
+        // doesn't make the value nullable
+        let property = DtoProperty {
+            oblivious: false,
+            ..property.clone()
+        };
+        let mut schema = property.validated_schema(types, config.localized);
+        if config.wrapper_structs == WrapperStructStyle::Brand {
+            schema.push_str(&format!(".brand<{}>()", ts_string(&self.name)));
+        }
+
+        let mut output = String::new();
+        if config.localized {
+            output.push_str(&format!("export const {}Schema = () => {{\n", self.name));
+            output.push_str("  const { t } = useI18n();\n");
+            output.push_str(&format!("  return {};\n}};\n", schema));
+        } else {
+            output.push_str(&format!("export const {}Schema = {};\n", self.name, schema));
+        }
+        output.push_str(&format!(
+            "\nexport type {} = z.infer<typeof {}Schema>;\n",
+            self.name, self.name
+        ));
+        output
+    }
+
+    fn is_update_dto(&self) -> bool {
+        self.name.starts_with("Update")
+    }
+
+    fn to_zod_schema_file(
+        dtos: &[Self],
+        interfaces: &[CSharpInterface],
+        enums: &TypeRegistry,
+        symbols: &SymbolTable,
+        output_dir: &Path,
+        config: &Config,
+    ) -> String {
+        let mut output = String::new();
+        output.push_str(&generate_file_header(config, "Zod Schema"));
+
+        // Add imports
+        output.push_str("import { z } from 'zod';\n");
+
+        // i18n import if localized
+        if config.localized {
+            output.push_str(&format!(
+                "import {{ useI18n }} from '{}';\n",
+                config.i18n_library
+            ));
+        }
+
+        // Additional imports, then those of mapped types used in this file
+        let mut mapped_imports = Vec::new();
+        for property in dtos
+            .iter()
+            .flat_map(|dto| &dto.properties)
+            .chain(interfaces.iter().flat_map(|i| &i.properties))
+        {
+            property
+                .type_name
+                .collect_mapped_imports(&mut mapped_imports);
+        }
+        for import in config.additional_imports.iter().chain(
+            mapped_imports
+                .into_iter()
+                .filter(|import| !config.additional_imports.contains(import)),
+        ) {
+            output.push_str(&format!("import {} from '{}';\n", import.name, import.path));
+        }
+
+        // Types declared in other files are imported from wherever they are
+        // generated; bases unknown to the project are expected in their own
+        // `<Name>.schema.ts`
+        let is_local = |name: &str| {
+            dtos.iter().any(|dto| dto.full_name == name)
+                || interfaces.iter().any(|i| i.full_name == name)
+        };
+        let mut external: Vec<(String, Vec<String>)> = Vec::new();
+        // A value import of an enum also covers its use as a type
+        let mut require = |module: String, symbol: String| match external
+            .iter_mut()
+            .find(|(m, _)| *m == module)
+        {
+            Some((_, symbols)) => {
+                let value = symbol.strip_prefix("type ").unwrap_or(&symbol);
+                let type_only = format!("type {}", symbol);
+                if symbols.iter().any(|s| *s == symbol || s == value) {
+                    // Already imported
+                } else if let Some(existing) = symbols.iter_mut().find(|s| **s == type_only) {
+                    *existing = symbol;
+                } else {
+                    symbols.push(symbol);
+                }
+            }
+            None => external.push((module, vec![symbol])),
+        };
+        for dto in dtos {
+            for base in dto.bases().filter(|base| !is_local(base)) {
+                let (module, mut symbol) = symbols
+                    .import(base, SymbolUsage::Schema, output_dir, config)
+                    .unwrap_or_else(|| {
+                        let base = simple_name(base);
+                        (format!("./{}.schema", base), format!("{}Schema", base))
+                    });
+                if enums.object_schemas.contains(base) {
+                    symbol = format!("{}ObjectSchema", simple_name(base));
+                }
+                require(module, symbol);
+            }
+            let mut references = Vec::new();
+            for property in &dto.properties {
+                property.type_name.collect_references(&mut references, true);
+            }
+            // Recursive schemas spell out their type, which names the others
+            let usages: &[SymbolUsage] = if dto.declares_type(enums) {
+                references.extend(dto.bases());
+                &[SymbolUsage::Schema, SymbolUsage::Type]
+            } else {
+                &[SymbolUsage::Schema]
+            };
+            for name in references.into_iter().filter(|name| !is_local(name)) {
+                for usage in usages {
+                    if let Some((module, symbol)) = symbols.import(name, *usage, output_dir, config)
+                    {
+                        require(module, symbol);
+                    }
+                }
+            }
+        }
+        for interface in interfaces {
+            for base in interface.bases().filter(|base| !is_local(base)) {
+                let (module, symbol) = symbols
+                    .import(base, SymbolUsage::Type, output_dir, config)
+                    .unwrap_or_else(|| {
+                        let base = simple_name(base);
+                        (format!("./{}.schema", base), format!("type {}", base))
+                    });
+                require(module, symbol);
+            }
+            let mut references = Vec::new();
+            for property in &interface.properties {
+                property.type_name.collect_references(&mut references, true);
+            }
+            for name in references.into_iter().filter(|name| !is_local(name)) {
+                if let Some((module, symbol)) =
+                    symbols.import(name, SymbolUsage::Type, output_dir, config)
+                {
+                    require(module, symbol);
+                }
+            }
+        }
+        for (module, symbols) in &external {
+            output.push_str(&format!(
+                "import {{ {} }} from '{}';\n",
+                symbols.join(", "),
+                module
+            ));
+        }
+
+        for interface in interfaces {
+            output.push('\n');
+            output.push_str(&interface.to_typescript(enums));
+        }
+
+        for dto in Self::in_declaration_order(dtos) {
+            output.push('\n');
+            output.push_str(&dto.to_zod_schema(enums, config));
+        }
+
+        output
+    }
+
+    /// Orders DTOs so that schemas are declared before the schemas extending
+    /// or eagerly referring to them
+    fn in_declaration_order(dtos: &[Self]) -> Vec<&Self> {
+        let mut ordered: Vec<&Self> = Vec::with_capacity(dtos.len());
+        let mut remaining: Vec<&Self> = dtos.iter().collect();
+        while !remaining.is_empty() {
+            let ready = remaining.iter().position(|dto| {
+                dto.dependencies().iter().all(|dependency| {
+                    *dependency == dto.full_name
+                        || ordered.iter().any(|d| d.full_name == *dependency)
+                        || !remaining.iter().any(|d| d.full_name == *dependency)
+                })
+            });
+            let Some(ready) = ready else {
+                // References within a cycle are lazy, so only types inheriting
+                // from each other, which C# rejects, leave nothing ready. They
+                // follow the ordered schemas in their source order
+                ordered.append(&mut remaining);
+                break;
+            };
+            ordered.push(remaining.remove(ready));
+        }
+        ordered
+    }
+
+    /// Schemas that have to be defined before this one
+    fn dependencies(&self) -> Vec<&str> {
+        let mut dependencies: Vec<&str> = self.bases().collect();
+        for property in &self.properties {
+            property
+                .type_name
+                .collect_references(&mut dependencies, false);
+        }
+        dependencies
+    }
+
+    /// Wraps references back into this schema's reference cycle in `z.lazy`
+    fn defer_cyclic_references(self, types: &TypeRegistry) -> Self {
+        let properties = self
+            .properties
+            .into_iter()
+            .map(|prop| DtoProperty {
+                type_name: prop
+                    .type_name
+                    .defer_references(&|name| types.in_same_cycle(&self.full_name, name)),
+                ..prop
+            })
+            .collect();
+        Self { properties, ..self }
+    }
+
+    /// Schemas in a reference cycle can't have their type inferred, so it is
+    /// declared up front and the schema annotated with it
+    fn declares_type(&self, types: &TypeRegistry) -> bool {
+        self.type_parameters.is_empty() && types.in_same_cycle(&self.full_name, &self.full_name)
+    }
+
+    /// TypeScript interface matching the schema's output
+    fn to_typescript_interface(&self, types: &TypeRegistry) -> String {
+        let mut output = format!("export interface {}", self.name);
+        if !self.type_parameters.is_empty() {
+            output.push_str(&format!("<{}>", self.type_parameters.join(", ")));
+        }
+        let bases: Vec<String> = self
+            .base_type
+            .iter()
+            .chain(&self.mixins)
+            .map(CSharpType::to_typescript_type)
+            .collect();
+        if !bases.is_empty() {
+            output.push_str(&format!(" extends {}", bases.join(", ")));
+        }
+        output.push_str(" {\n");
+
+        for prop in &self.properties {
+            if let Some(doc) = &prop.documentation {
+                output.push_str(&jsdoc(doc, "  ", true));
+            }
+            let optional = self.is_update_dto() && prop.default_chain(types).is_none();
+            output.push_str(&format!(
+                "  {}{}: {};\n",
+                prop.name,
+                if optional { "?" } else { "" },
+                prop.validated_type(types).to_typescript_type()
+            ));
+        }
+
+        output.push_str("}\n\n");
+        output
+    }
+
+    /// Names of the schemas this one builds on
+    fn bases(&self) -> impl Iterator<Item = &str> {
+        self.base_type
+            .iter()
+            .chain(&self.mixins)
+            .filter_map(|base| base.schema_name())
+    }
+
+    /// Single-field structs such as `readonly record struct StudentId(Guid Value)`
+    fn wrapped_property(&self) -> Option<&DtoProperty> {
+        match self.properties.as_slice() {
+            [property] if self.is_struct && self.type_parameters.is_empty() => Some(property),
+            _ => None,
+        }
+    }
+
+    fn to_zod_schema(&self, types: &TypeRegistry, config: &Config) -> String {
+        let mut output = String::new();
+
+        let is_update = self.is_update_dto();
+
+        // Add documentation if available
+        if let Some(doc) = &self.documentation {
+            output.push_str(&jsdoc(doc, "", false));
+        }
+
+        if let Some(property) = self.wrapped_property() {
+            if config.wrapper_structs != WrapperStructStyle::Object {
+                output.push_str(&self.to_wrapper_schema(property, types, config));
+                return output;
+            }
+        }
+
+        let declares_type = self.declares_type(types);
+        let annotation = if !declares_type {
+            String::new()
+        } else if self
+            .properties
+            .iter()
+            .any(|p| p.default_chain(types).is_some())
+        {
+            // Defaults make the input type differ from the declared output
+            format!(": z.ZodType<{}, z.ZodTypeDef, unknown>", self.name)
+        } else {
+            format!(": z.ZodType<{}>", self.name)
+        };
+        // Generic types are declared as plain TypeScript generics, so that
+        // interfaces and other types can pass them TypeScript types
+        if (declares_type || !self.type_parameters.is_empty()) && !self.is_interface {
+            output.push_str(&self.to_typescript_interface(types));
+        }
+
+        let mut bases = self
+            .base_type
+            .iter()
+            .chain(&self.mixins)
+            .map(|base| match base {
+                CSharpType::Custom(name) if types.object_schemas.contains(name) => {
+                    let call = if config.localized { "()" } else { "" };
+                    format!("{}ObjectSchema{}", simple_name(name), call)
+                }
+                base => base.schema_reference(types, config.localized),
+            });
+        let object = match bases.next() {
+            Some(first) => {
+                let merged: String = bases.map(|base| format!(".merge({})", base)).collect();
+                format!("{}{}.extend", first, merged)
+            }
+            None => "z.object".to_string(),
+        };
+
+        // Generic DTOs become factories taking a schema per type parameter
+        let signature = if self.type_parameters.is_empty() {
+            "()".to_string()
+        } else {
+            format!(
+                "<{}>({})",
+                self.type_parameters
+                    .iter()
+                    .map(|p| format!("{} extends z.ZodTypeAny", p))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                self.type_parameters
+                    .iter()
+                    .map(|p| format!("{}: {}", type_parameter_argument(p), p))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        };
+
+        // A `z.ZodType` annotation hides the object's methods, so recursive
+        // schemas that others extend are first declared without one
+        let has_object_schema = types.object_schemas.contains(&self.full_name);
+        let (schema_name, object_annotation) = if has_object_schema {
+            (format!("{}ObjectSchema", self.name), "")
+        } else {
+            (format!("{}Schema", self.name), annotation.as_str())
+        };
+        if config.localized {
+            output.push_str(&format!(
+                "export const {} = {}{} => {{\n",
+                schema_name, signature, object_annotation
+            ));
+            output.push_str("  const { t } = useI18n();\n");
+            output.push_str(&format!("  return {}({{\n", object));
+        } else if self.type_parameters.is_empty() {
+            output.push_str(&format!(
+                "export const {}{} = {}({{\n",
+                schema_name, object_annotation, object
+            ));
+        } else {
+            output.push_str(&format!(
+                "export const {}Schema = {} =>\n  {}({{\n",
+                self.name, signature, object
+            ));
+        }
+
+        // Generate properties
+        for prop in &self.properties {
+            if let Some(doc) = &prop.documentation {
+                output.push_str(&jsdoc(doc, "    ", true));
+            }
+
+            output.push_str(&format!(
+                "    {}: {},\n",
+                prop.name,
+                prop.to_zod_schema(types, config.localized, is_update)
+            ));
+        }
+
+        if config.localized {
+            output.push_str("  });\n};\n");
+        } else if self.type_parameters.is_empty() {
+            output.push_str("});\n");
+        } else {
+            output.push_str("  });\n");
+        }
+        if has_object_schema && config.localized {
+            output.push_str(&format!(
+                "\nexport const {}Schema = (){} => {}();\n",
+                self.name, annotation, schema_name
+            ));
+        } else if has_object_schema {
+            output.push_str(&format!(
+                "\nexport const {}Schema{} = {};\n",
+                self.name, annotation, schema_name
+            ));
+        }
+
+        if self.is_interface || declares_type || !self.type_parameters.is_empty() {
+            // The interface declaration already provides the type
+        } else {
+            output.push_str(&format!(
+                "\nexport type {} = z.infer<typeof {}Schema>;\n",
+                self.name, self.name
+            ));
+        }
+
+        output
+    }
+}
+
+pub fn process_directory(
+    processor: &mut FileProcessor,
+    dir_path: &Path,
+    input_root: &Path,
+    output_root: &Path,
+    config: &Config,
+) -> std::io::Result<()> {
+    let mut paths = Vec::new();
+    collect_source_files(dir_path, config, &mut paths)?;
+    processor.process_files(&paths, input_root, output_root, config)
+}
+
+/// Source files under `path` in a stable order, so the same file always
+/// owns a partial type
+fn collect_source_files(
+    path: &Path,
+    config: &Config,
+    paths: &mut Vec<PathBuf>,
+) -> std::io::Result<()> {
+    if path.is_dir() {
+        let mut entries = fs::read_dir(path)?
+            .map(|entry| entry.map(|e| e.path()))
+            .collect::<std::io::Result<Vec<_>>>()?;
+        entries.sort();
+        for entry in entries {
+            collect_source_files(&entry, config, paths)?;
+        }
+    } else if config.is_valid_extension(path) && !config.should_ignore(path) {
+        paths.push(path.to_path_buf());
+    }
+
+    Ok(())
+}
+
+pub fn process_single_file(
+    processor: &mut FileProcessor,
+    input_path: &Path,
+    output_dir: &Path,
+    config: &Config,
+) -> std::io::Result<()> {
+    let input_root = config
+        .input_dir
+        .as_deref()
+        .unwrap_or_else(|| input_path.parent().unwrap_or(Path::new("")));
+
+    if input_path.is_dir() {
+        process_directory(processor, input_path, input_root, output_dir, config)
+    } else {
+        processor.process_file(input_path, input_root, output_dir, config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Scratch C# project on disk with sources under `cs/` and generated
+    /// files under `out/`, removed when dropped
+    struct Project {
+        root: PathBuf,
+    }
+
+    impl Project {
+        fn new() -> Self {
+            static NEXT: AtomicUsize = AtomicUsize::new(0);
+            let root = std::env::temp_dir().join(format!(
+                "code_gen-test-{}-{}",
+                std::process::id(),
+                NEXT.fetch_add(1, Ordering::SeqCst)
+            ));
+            fs::create_dir_all(root.join("cs")).unwrap();
+            Self { root }
+        }
+
+        fn with_files(files: &[(&str, &str)]) -> Self {
+            let project = Self::new();
+            for (path, source) in files {
+                project.write(path, source);
+            }
+            project
+        }
+
+        fn write(&self, path: &str, source: &str) {
+            let path = self.root.join("cs").join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, source).unwrap();
+        }
+
+        fn generate(&self, processor: &mut FileProcessor, config: &Config) {
+            let input = self.root.join("cs");
+            process_directory(processor, &input, &input, &self.root.join("out"), config).unwrap();
+        }
+
+        /// Generates every file once with a fresh processor
+        fn generate_all(&self, config: &Config) {
+            self.generate(&mut FileProcessor::new(), config);
+        }
+
+        fn output(&self, path: &str) -> String {
+            let path = self.root.join("out").join(path);
+            fs::read_to_string(&path)
+                .unwrap_or_else(|_| panic!("{} was not generated", path.display()))
+        }
+
+        fn exists(&self, path: &str) -> bool {
+            self.root.join("out").join(path).exists()
+        }
+    }
+
+    impl Drop for Project {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.root);
+        }
+    }
+
+    /// Field names of the object declared for `name`'s schema, in order
+    fn schema_fields(schemas: &str, name: &str) -> Vec<String> {
+        let start = schemas
+            .find(&format!("export const {}Schema", name))
+            .unwrap_or_else(|| panic!("{}Schema was not generated", name));
+        schemas[start..]
+            .lines()
+            .skip(1)
+            .take_while(|line| !line.starts_with('}'))
+            .filter_map(|line| line.trim().split_once(':'))
+            .map(|(field, _)| field.to_string())
+            .collect()
+    }
+
+    /// Generates a record with one field per C# type in `cases` and checks the
+    /// schema each field maps to
+    fn assert_type_mappings(cases: &[(&str, &str)]) {
+        let parameters: Vec<String> = cases
+            .iter()
+            .enumerate()
+            .map(|(i, (type_name, _))| format!("{} Field{}", type_name, i))
+            .collect();
+        let project = Project::with_files(&[(
+            "MappingsDto.cs",
+            &format!("public record MappingsDto({});", parameters.join(", ")),
+        )]);
+        project.generate_all(&Config::default());
+        let schemas = project.output("MappingsDto.schema.ts");
+
+        for (i, (type_name, expected)) in cases.iter().enumerate() {
+            let prefix = format!("    Field{}: ", i);
+            let mapped = schemas
+                .lines()
+                .find_map(|line| line.strip_prefix(&prefix))
+                .map(|schema| schema.trim_end_matches(','));
+            assert_eq!(mapped, Some(*expected), "{}", type_name);
+        }
+    }
+
+    #[test]
+    fn generates_enums_and_schemas() {
+        let project = Project::with_files(&[
+            (
+                "Status.cs",
+                r#"
+                namespace App;
+
+                /// <summary>Enrollment state</summary>
+                public enum Status
+                {
+                    [Display(Name = "Is active")]
+                    Active,
+                    Inactive,
+                }
+                "#,
+            ),
+            (
+                "StudentDto.cs",
+                r#"
+                namespace App;
+
+                public record StudentDto(
+                    [Range(1, 12, ErrorMessage = "Grade (1-12)")] int Grade,
+                    string Name);
+                "#,
+            ),
+        ]);
+        project.generate_all(&Config::default());
+
+        let enums = project.output("Status.ts");
+        assert!(enums.contains("/**\n * Enrollment state\n */\nexport enum Status {"));
+        assert!(enums.contains("  Active = 'Is active',\n  Inactive = 'Inactive',\n"));
+
+        let schemas = project.output("StudentDto.schema.ts");
+        assert!(schemas.contains("    Grade: z.number().int()"));
+        assert!(schemas.contains(
+            ".min(1, { message: 'Grade (1-12)' }).max(12, { message: 'Grade (1-12)' }),\n"
+        ));
+        assert!(schemas.contains("    Name: z.string()"));
+        assert!(schemas.contains("export type StudentDto = z.infer<typeof StudentDtoSchema>;"));
+    }
+
+    #[test]
+    fn services_and_unparsable_files_produce_no_schema() {
+        let project = Project::with_files(&[
+            (
+                "Service.cs",
+                "public class StudentService { public void Run() { } }",
+            ),
+            ("Broken.cs", "public class {"),
+        ]);
+        project.generate_all(&Config::default());
+        assert!(!project.exists("Service.schema.ts"));
+        assert!(!project.exists("Broken.schema.ts"));
+    }
+
+    #[test]
+    fn classes_records_and_primary_constructors_become_dtos() {
+        let project = Project::with_files(&[(
+            "Dtos.cs",
+            r#"
+            public class StudentDto
+            {
+                public string Name { get; set; }
+                public int Age { get; init; }
+                private int Secret { get; set; }
+                public static int Count { get; set; }
+            }
+
+            public record PersonDto(string First, string Last)
+            {
+                public int Age { get; init; }
+            }
+
+            public class ItemDto(int Id, string Title)
+            {
+                public bool Done { get; set; }
+            }
+
+            public record RenamedDto(string name)
+            {
+                public string Name { get; init; } = name;
+            }
+            "#,
+        )]);
+        project.generate_all(&Config::default());
+        let schemas = project.output("Dtos.schema.ts");
+        assert_eq!(schema_fields(&schemas, "StudentDto"), ["Name", "Age"]);
+        assert_eq!(
+            schema_fields(&schemas, "PersonDto"),
+            ["First", "Last", "Age"]
+        );
+        assert_eq!(schema_fields(&schemas, "ItemDto"), ["Id", "Title", "Done"]);
+        // A parameter only feeding a property of the same name is not repeated
+        assert_eq!(schema_fields(&schemas, "RenamedDto"), ["Name"]);
+    }
+
+    #[test]
+    fn derived_dtos_extend_their_base_schema() {
+        let project = Project::with_files(&[
+            (
+                "Students.cs",
+                r#"
+                public record CreateStudentDto(int Grade) : PersonDto("x");
+                public abstract record PersonDto(string First);
+                "#,
+            ),
+            (
+                "Audited.cs",
+                "public class AuditedDto : EntityDto, IHasId { public int Version { get; set; } }",
+            ),
+        ]);
+        project.generate_all(&Config::default());
+
+        let students = project.output("Students.schema.ts");
+        assert!(students.contains("export const PersonDtoSchema = z.object({\n"));
+        assert!(
+            students.contains("export const CreateStudentDtoSchema = PersonDtoSchema.extend({\n")
+        );
+        // The base is declared first, whatever the source order
+        assert!(
+            students.find("PersonDtoSchema =").unwrap()
+                < students.find("CreateStudentDtoSchema =").unwrap()
+        );
+        assert!(!students.contains("import { PersonDtoSchema }"));
+
+        let audited = project.output("Audited.schema.ts");
+        assert!(audited.contains("import { EntityDtoSchema } from './EntityDto.schema';"));
+        assert!(audited.contains("export const AuditedDtoSchema = EntityDtoSchema.extend({\n"));
+        assert!(!audited.contains("IHasId"));
+    }
+
+    #[test]
+    fn generic_dtos_become_schema_factories() {
+        let project = Project::with_files(&[(
+            "Results.cs",
+            r#"
+            public record PagedResult<T>(List<T> Items, int Total);
+            public class Result<T, TError>
+            {
+                public T Value { get; set; }
+                public TError Error { get; set; }
+            }
+            public record StudentDto(string Name);
+            public record StudentPage(PagedResult<StudentDto> Students, Result<int, string> Outcome);
+            "#,
+        )]);
+        project.generate_all(&Config::default());
+        let schemas = project.output("Results.schema.ts");
+        assert!(schemas.contains(
+            "export const PagedResultSchema = <T extends z.ZodTypeAny>(item: T) =>\n  z.object({\n    Items: z.array(item)"
+        ));
+        assert!(schemas.contains(
+            "export const ResultSchema = <T extends z.ZodTypeAny, TError extends z.ZodTypeAny>(item: T, error: TError) =>\n"
+        ));
+        assert!(schemas.contains("    Error: error"));
+        assert!(schemas.contains("    Students: PagedResultSchema(StudentDtoSchema)"));
+        assert!(schemas.contains("    Outcome: ResultSchema(z.number().int(), z.string())"));
+    }
+
+    #[test]
+    fn nested_and_qualified_type_expressions() {
+        let project = Project::with_files(&[(
+            "Dto.cs",
+            r#"
+            public class Dto
+            {
+                public List<Dictionary<string, List<int>>> Map { get; set; }
+                public int[][] Jagged { get; set; }
+                public int[,] Grid { get; set; }
+                public Nullable<int> Maybe { get; set; }
+                public global::System.Collections.Generic.List<string> Names { get; set; }
+                public (int Id, string Name) Pair { get; set; }
+            }
+            "#,
+        )]);
+        project.generate_all(&Config::default());
+        let schemas = project.output("Dto.schema.ts");
+        for line in [
+            "    Map: z.array(z.record(z.string(), z.array(z.number().int())))",
+            "    Jagged: z.array(z.array(z.number().int()))",
+            "    Grid: z.array(z.array(z.number().int()))",
+            "    Maybe: z.number().int().nullable()",
+            "    Names: z.array(z.string())",
+            "    Pair: z.tuple([z.number().int(), z.string()])",
+        ] {
+            assert!(schemas.contains(line), "missing {}", line);
+        }
+    }
+
+    #[test]
+    fn enum_values_follow_their_underlying_type() {
+        let source = r#"
+            public enum Grade : byte { A = 1, B, C = B + 5, D = 1 << 3, E = A | D }
+            public enum Wrap : sbyte { Low = -128, High = 200, Next }
+            "#;
+        let project = Project::with_files(&[("Enums.cs", source)]);
+
+        project.generate_all(&Config::default());
+        let enums = project.output("Enums.ts");
+        assert!(enums.contains("export enum Grade {\n  A = 'A',\n  B = 'B',\n"));
+
+        project.generate_all(&Config {
+            enum_style: EnumStyle::Numeric,
+            ..Config::default()
+        });
+        let enums = project.output("Enums.ts");
+        assert!(enums
+            .contains("export enum Grade {\n  A = 1,\n  B = 2,\n  C = 7,\n  D = 8,\n  E = 9,\n}"));
+        assert!(
+            enums.contains("export enum Wrap {\n  Low = -128,\n  High = -56,\n  Next = -55,\n}")
+        );
+    }
+
+    #[test]
+    fn flags_enums_get_bitmask_helpers_and_schema() {
+        let project = Project::with_files(&[(
+            "Access.cs",
+            "[Flags] public enum Access : byte { None = 0, Read = 1, Write = 2, Execute = 4, All = Read | Write | Execute }",
+        )]);
+        project.generate_all(&Config::default());
+        let access = project.output("Access.ts");
+
+        assert!(access.contains(
+            "export enum Access {\n  None = 0,\n  Read = 1,\n  Write = 2,\n  Execute = 4,\n  All = 7,\n}"
+        ));
+        assert!(access.contains(
+            "export function hasFlag(value: number, flag: Access): boolean {\n    return ((value & flag) >>> 0) === flag;"
+        ));
+        assert!(access.contains("export function combineFlags(...flags: Access[]): number {"));
+        assert!(access.contains(
+            "return [Access.Read, Access.Write, Access.Execute].filter((flag) => hasFlag(value, flag));"
+        ));
+        assert!(access
+            .contains(".min(0)\n  .max(7)\n  .refine((value) => ((value & ~7) >>> 0) === 0, {"));
+    }
+
+    #[test]
+    fn single_field_structs_follow_the_wrapper_style() {
+        let source = r#"
+            public readonly record struct StudentId(Guid Value);
+            public struct Point { public int X { get; set; } public int Y { get; set; } }
+            "#;
+        let project = Project::with_files(&[("Ids.cs", source)]);
+        let cases = [
+            (
+                WrapperStructStyle::Object,
+                "export const StudentIdSchema = z.object({\n    Value: z.string().uuid()",
+            ),
+            (
+                WrapperStructStyle::Unwrap,
+                "export const StudentIdSchema = z.string().uuid();",
+            ),
+            (
+                WrapperStructStyle::Brand,
+                "export const StudentIdSchema = z.string().uuid().brand<'StudentId'>();",
+            ),
+        ];
+        for (wrapper_structs, expected) in cases {
+            project.generate_all(&Config {
+                wrapper_structs,
+                ..Config::default()
+            });
+            let schemas = project.output("Ids.schema.ts");
+            assert!(
+                schemas.contains(expected),
+                "{wrapper_structs:?}:\n{schemas}"
+            );
+            assert_eq!(schema_fields(&schemas, "Point"), ["X", "Y"]);
+        }
+    }
+
+    #[test]
+    fn interfaces_become_typescript_interfaces() {
+        let source = r#"
+            public interface IEntity { int Id { get; } }
+            public interface IStudent : IEntity { string Name { get; set; } string? Nickname { get; } void Save(); }
+            public interface IRepository<T> : IEntity where T : class { T? Find(int id); List<T> Items { get; } }
+            "#;
+        let project = Project::with_files(&[("Contracts.cs", source)]);
+
+        project.generate_all(&Config::default());
+        let contracts = project.output("Contracts.schema.ts");
+        assert!(contracts.contains("export interface IEntity {\n  Id: number;\n}"));
+        assert!(contracts.contains(
+            "export interface IStudent extends IEntity {\n  Name: string;\n  Nickname: string | null;\n}"
+        ));
+        assert!(contracts
+            .contains("export interface IRepository<T> extends IEntity {\n  Items: T[];\n}"));
+        assert!(!contracts.contains("IEntitySchema"));
+
+        project.generate_all(&Config {
+            interface_schemas: true,
+            ..Config::default()
+        });
+        let contracts = project.output("Contracts.schema.ts");
+        assert!(contracts.contains("export const IEntitySchema = z.object({"));
+        assert!(contracts.contains("export const IStudentSchema = IEntitySchema.extend({"));
+        assert!(contracts.contains(
+            "export const IRepositorySchema = <T extends z.ZodTypeAny>(item: T) =>\n  IEntitySchema.extend({"
+        ));
+        assert_eq!(schema_fields(&contracts, "IStudent"), ["Name", "Nickname"]);
+    }
+
+    #[test]
+    fn namespace_layout_maps_namespaces_to_folders() {
+        let project = Project::with_files(&[
+            (
+                "Grades.cs",
+                "namespace UniTrack.Contracts.StudentGrades; public record GradeDto(int Value);",
+            ),
+            (
+                "Nested/Courses.cs",
+                "namespace UniTrack { namespace Contracts.CourseAPI { public record CourseDto(string Title); } }",
+            ),
+            ("Root.cs", "namespace UniTrack.Contracts { public record RootDto(int Id); }"),
+            ("Other.cs", "namespace Vendor.Shared; public record OtherDto(int Id);"),
+        ]);
+        project.generate_all(&Config {
+            output_layout: OutputLayout::Namespace,
+            namespace_prefix: Some("UniTrack.Contracts".to_string()),
+            ..Config::default()
+        });
+
+        assert!(project.exists("student-grades/Grades.schema.ts"));
+        assert!(project.exists("course-api/Courses.schema.ts"));
+        assert!(project.exists("Root.schema.ts"));
+        assert!(project.exists("vendor/shared/Other.schema.ts"));
+        assert!(!project.exists("Nested/Courses.schema.ts"));
+    }
+
+    #[test]
+    fn xml_doc_comments_become_jsdoc() {
+        let source = r#"
+            /// <summary>Lifecycle of a <see cref="StudentDto"/></summary>
+            public enum Status
+            {
+                /// <summary>Currently enrolled</summary>
+                Active,
+                Inactive // trailing note
+            }
+
+            /// <summary>A student</summary>
+            /// <remarks>Read only</remarks>
+            public record StudentDto
+            {
+                /// <summary>Full name</summary>
+                /// <example>Ada</example>
+                public string Name { get; init; }
+                public int Age { get; init; } // not a doc comment
+                /// <summary>Stray</summary>
+            }
+            "#;
+        let project = Project::with_files(&[("Students.cs", source)]);
+        project.generate_all(&Config::default());
+
+        let enums = project.output("Students.ts");
+        assert!(
+            enums.contains("/**\n * Lifecycle of a {@link StudentDto}\n */\nexport enum Status {")
+        );
+        assert!(enums.contains(
+            "  /** Currently enrolled */\n  Active = 'Active',\n  Inactive = 'Inactive',"
+        ));
+
+        let schemas = project.output("Students.schema.ts");
+        assert!(schemas.contains(
+            "/**\n * A student\n *\n * @remarks Read only\n */\nexport const StudentDtoSchema"
+        ));
+        assert!(schemas.contains(
+            "    /**\n     * Full name\n     *\n     * @example\n     * Ada\n     */\n    Name: z.string()"
+        ));
+        assert!(!schemas.contains("Stray"));
+        assert!(!schemas.contains("not a doc comment"));
+    }
+
+    #[test]
+    fn members_follow_the_defined_symbols() {
+        let source = r#"
+            public class ConfigDto
+            {
+                #region Common
+                public int Port { get; set; }
+                #endregion
+            #if DEBUG
+                public string DebugToken { get; set; }
+            #else
+                public string ReleaseToken { get; set; }
+            #endif
+            }
+            "#;
+        let project = Project::with_files(&[("ConfigDto.cs", source)]);
+        project.generate_all(&Config::default());
+        let schemas = project.output("ConfigDto.schema.ts");
+        assert_eq!(
+            schema_fields(&schemas, "ConfigDto"),
+            ["Port", "ReleaseToken"]
+        );
+
+        project.generate_all(&Config {
+            defined_symbols: vec!["DEBUG".to_string()],
+            ..Config::default()
+        });
+        let schemas = project.output("ConfigDto.schema.ts");
+        assert_eq!(schema_fields(&schemas, "ConfigDto"), ["Port", "DebugToken"]);
+    }
+
+    #[test]
+    fn partial_types_merge_into_the_first_file() {
+        let project = Project::with_files(&[
+            (
+                "A/StudentDto.cs",
+                r#"
+                namespace App;
+
+                /// <summary>A student</summary>
+                public partial class StudentDto
+                {
+                    public int Id { get; set; }
+                }
+                "#,
+            ),
+            (
+                "B/StudentDto.Generated.cs",
+                r#"
+                namespace App
+                {
+                    public partial class StudentDto : PersonDto
+                    {
+                        public int Grade { get; set; }
+                    }
+
+                    public partial class Other { public int X { get; set; } }
+                }
+                "#,
+            ),
+        ]);
+        let mut processor = FileProcessor::new();
+        project.generate(&mut processor, &Config::default());
+
+        let schemas = project.output("A/StudentDto.schema.ts");
+        assert!(schemas.contains(
+            "/**\n * A student\n */\nexport const StudentDtoSchema = PersonDtoSchema.extend({"
+        ));
+        assert_eq!(schema_fields(&schemas, "StudentDto"), ["Id", "Grade"]);
+        let generated = project.output("B/StudentDto.Generated.schema.ts");
+        assert!(!generated.contains("StudentDto"));
+        assert!(generated.contains("OtherSchema"));
+
+        // Editing either part regenerates the file owning the type
+        project.write(
+            "B/StudentDto.Generated.cs",
+            "namespace App { public partial class StudentDto { public int Year { get; set; } } }",
+        );
+        project.generate(&mut processor, &Config::default());
+        let schemas = project.output("A/StudentDto.schema.ts");
+        assert_eq!(schema_fields(&schemas, "StudentDto"), ["Id", "Year"]);
+        assert!(!project.exists("B/StudentDto.Generated.schema.ts"));
+    }
+
+    #[test]
+    fn partial_types_in_different_namespaces_stay_apart() {
+        let project = Project::with_files(&[
+            (
+                "One.cs",
+                "namespace App.One { public partial class Dto { public int A { get; set; } } }",
+            ),
+            (
+                "Two.cs",
+                "namespace App.Two { public partial class Dto { public int B { get; set; } } }",
+            ),
+        ]);
+        project.generate_all(&Config::default());
+        assert_eq!(
+            schema_fields(&project.output("One.schema.ts"), "Dto"),
+            ["A"]
+        );
+        assert_eq!(
+            schema_fields(&project.output("Two.schema.ts"), "Dto"),
+            ["B"]
+        );
+    }
+
+    #[test]
+    fn defaults_and_initializers_become_zod_defaults() {
+        let source = r#"
+            public enum Level { Low, High }
+            public record SearchDto(string Culture = "en", int Page = 1, bool Archived = false, Level Level = Level.High, double Ratio = 0.5);
+            public class SettingsDto
+            {
+                public List<string> Tags { get; set; } = [];
+                public string[] Codes { get; set; } = new[] { "a", "b" };
+                public Dictionary<string, int> Map { get; set; } = new();
+                public string? Note { get; set; } = null;
+                public string Computed { get; set; } = Compute();
+            }
+            "#;
+        let project = Project::with_files(&[("Search.cs", source)]);
+        project.generate_all(&Config::default());
+        let schemas = project.output("Search.schema.ts");
+
+        for line in [
+            "    Culture: z.string().default('en'),",
+            "    Page: z.number().int().default(1),",
+            "    Archived: z.boolean().default(false),",
+            "    Level: z.nativeEnum(Level).default(Level.High),",
+            "    Ratio: z.number().default(0.5),",
+            "    Tags: z.array(z.string()).default([]),",
+            "    Codes: z.array(z.string()).default(['a', 'b']),",
+            "    Map: z.record(z.string(), z.number().int()).default({}),",
+            "    Note: z.string().nullable().default(null),",
+        ] {
+            assert!(schemas.contains(line), "missing {line}:\n{schemas}");
+        }
+        assert!(!schemas.contains("Compute()"));
+    }
+
+    #[test]
+    fn obsolete_members_are_deprecated_or_dropped() {
+        let source = r#"
+            [Obsolete("Use Level instead")]
+            public enum Grade { A, [Obsolete] B, C }
+            public enum Level { Low, [Obsolete("Gone")] Mid, High }
+            [Obsolete]
+            public record OldDto(int Id);
+            public class StudentDto
+            {
+                public int Id { get; set; }
+                [Obsolete("Use Id")] public int LegacyId { get; set; }
+            }
+            "#;
+        let project = Project::with_files(&[("School.cs", source)]);
+
+        project.generate_all(&Config::default());
+        let enums = project.output("School.ts");
+        assert!(enums.contains("/**\n * @deprecated Use Level instead\n */\nexport enum Grade {"));
+        assert!(enums.contains("  /** @deprecated */\n  B = 'B',"));
+        assert!(enums.contains("  /** @deprecated Gone */\n  Mid = 'Mid',"));
+        let schemas = project.output("School.schema.ts");
+        assert!(schemas.contains("/**\n * @deprecated\n */\nexport const OldDtoSchema"));
+        assert!(schemas.contains("    /** @deprecated Use Id */\n    LegacyId: z.number().int()"));
+
+        project.generate_all(&Config {
+            drop_obsolete: true,
+            ..Config::default()
+        });
+        let enums = project.output("School.ts");
+        assert!(!enums.contains("Grade"));
+        assert!(enums.contains("export enum Level {\n  Low = 'Low',\n  High = 'High',\n}"));
+        let schemas = project.output("School.schema.ts");
+        assert!(!schemas.contains("OldDto"));
+        assert_eq!(schema_fields(&schemas, "StudentDto"), ["Id"]);
+    }
+
+    #[test]
+    fn constant_classes_become_const_objects() {
+        let source = r#"
+            public static class Roles
+            {
+                public const string Admin = "admin";
+                public static readonly string Teacher = "teacher";
+                public const int MaxStudents = 30;
+                public const double Ratio = 1.5;
+                public const bool Enabled = true;
+                public static readonly TimeSpan Timeout = TimeSpan.FromSeconds(5);
+                public static readonly string Computed = Build();
+                public static string Mutable = "x";
+                private const string Secret = "s";
+                internal const string Hidden = "h";
+                public string Instance { get; set; }
+            }
+            "#;
+        let project = Project::with_files(&[("Roles.cs", source)]);
+        project.generate_all(&Config::default());
+
+        let roles = project.output("Roles.ts");
+        assert!(roles.contains(
+            "export const Roles = {\n  Admin: 'admin',\n  Teacher: 'teacher',\n  MaxStudents: 30,\n  Ratio: 1.5,\n  Enabled: true,\n} as const;"
+        ));
+        assert!(roles.contains("export type Roles = (typeof Roles)[keyof typeof Roles];"));
+        for skipped in [
+            "Timeout", "Computed", "Mutable", "Secret", "Hidden", "Instance",
+        ] {
+            assert!(!roles.contains(skipped), "{skipped} should be skipped");
+        }
+    }
+
+    #[test]
+    fn using_aliases_resolve_across_files() {
+        let project = Project::with_files(&[
+            ("Usings.cs", "global using StudentId = System.Guid;"),
+            (
+                "StudentDto.cs",
+                r#"
+                using Scores = System.Collections.Generic.Dictionary<string, int>;
+                namespace App
+                {
+                    using Tags = System.Collections.Generic.List<string>;
+                    public record StudentDto(StudentId Id, Scores Scores, Tags Tags, List<StudentId> Friends);
+                }
+                "#,
+            ),
+        ]);
+        project.generate_all(&Config::default());
+        let schemas = project.output("StudentDto.schema.ts");
+        for line in [
+            "    Id: z.string().uuid()",
+            "    Scores: z.record(z.string(), z.number().int())",
+            "    Tags: z.array(z.string())",
+            "    Friends: z.array(z.string().uuid())",
+        ] {
+            assert!(schemas.contains(line), "missing {line}:\n{schemas}");
+        }
+    }
+
+    #[test]
+    fn primitive_and_date_types_map_to_zod() {
+        let cases = [
+            ("byte", "z.number().int().min(0).max(255)"),
+            ("sbyte", "z.number().int().min(-128).max(127)"),
+            ("short", "z.number().int().min(-32768).max(32767)"),
+            ("ushort", "z.number().int().min(0).max(65535)"),
+            ("uint", "z.number().int().min(0).max(4294967295)"),
+            ("long", "z.number().int().safe()"),
+            ("ulong", "z.number().int().nonnegative().safe()"),
+            ("float", "z.number()"),
+            ("decimal", "z.number()"),
+            ("char", "z.string().length(1)"),
+            ("Uri", "z.string().url()"),
+            ("DateTimeOffset", "z.string().datetime({ offset: true })"),
+            ("DateOnly", "z.string().date()"),
+            ("TimeOnly", "z.string().time()"),
+            ("TimeSpan", "z.string().duration()"),
+            ("byte[]", "z.string().base64()"),
+        ];
+        assert_type_mappings(&cases);
+    }
+
+    #[test]
+    fn collection_family_maps_to_zod() {
+        let unique = ".refine((items) => new Set(items).size === items.length, { message: 'Items must be unique' })";
+        assert_type_mappings(&[
+            ("HashSet<string>", &format!("z.array(z.string()){}", unique)),
+            ("ISet<int>", &format!("z.array(z.number().int()){}", unique)),
+            (
+                "IReadOnlySet<Guid>",
+                &format!("z.array(z.string().uuid()){}", unique),
+            ),
+            (
+                "KeyValuePair<string, int>",
+                "z.object({ Key: z.string(), Value: z.number().int() })",
+            ),
+            ("(int, string)", "z.tuple([z.number().int(), z.string()])"),
+            (
+                "(int Id, bool Done)",
+                "z.tuple([z.number().int(), z.boolean()])",
+            ),
+            (
+                "ValueTuple<int, bool>",
+                "z.tuple([z.number().int(), z.boolean()])",
+            ),
+            (
+                "Tuple<int, string>",
+                "z.tuple([z.number().int(), z.string()])",
+            ),
+            ("IEnumerable<int>", "z.array(z.number().int())"),
+            ("IReadOnlyList<string>", "z.array(z.string())"),
+            ("ICollection<int>", "z.array(z.number().int())"),
+            (
+                "IDictionary<string, int>",
+                "z.record(z.string(), z.number().int())",
+            ),
+            (
+                "IReadOnlyDictionary<Guid, string>",
+                "z.record(z.string().uuid(), z.string())",
+            ),
+            (
+                "Dictionary<int, string>",
+                "z.record(z.coerce.number().int(), z.string())",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn type_mappings_replace_built_in_types() {
+        let config: Config = toml::from_str(
+            r#"
+            [type_mappings]
+            Guid = { zod = "z.string().cuid()", typescript = "string" }
+            "NodaTime.Instant" = { zod = "z.string().datetime()", typescript = "string" }
+            Money = { zod = "MoneySchema", typescript = "Money", import = { name = "{ MoneySchema, type Money }", path = "@/schemas/money" } }
+            "Pair<K, V>" = { zod = "PairSchema({K}, {V})", typescript = "Pair<{K}, {V}>" }
+            "#,
+        )
+        .unwrap();
+        let project = Project::with_files(&[
+            (
+                "OrderDto.cs",
+                "public record OrderDto(Guid Id, NodaTime.Instant At, List<Money> Items, Pair<int, StudentDto> Best);",
+            ),
+            ("IOrder.cs", "public interface IOrder { Money Total { get; } Pair<string, int> Split { get; } }"),
+            ("StudentDto.cs", "public record StudentDto(string Name);"),
+        ]);
+        project.generate_all(&config);
+
+        let order = project.output("OrderDto.schema.ts");
+        assert!(order.contains("import { MoneySchema, type Money } from '@/schemas/money';"));
+        for line in [
+            "    Id: z.string().cuid()",
+            "    At: z.string().datetime()",
+            "    Items: z.array(MoneySchema)",
+            "    Best: PairSchema(z.number().int(), StudentDtoSchema)",
+        ] {
+            assert!(order.contains(line), "missing {line}:\n{order}");
+        }
+
+        let interface = project.output("IOrder.schema.ts");
+        assert!(interface.contains("import { MoneySchema, type Money } from '@/schemas/money';"));
+        assert!(interface.contains("  Total: Money;\n  Split: Pair<string, number>;"));
+        assert!(!project
+            .output("StudentDto.schema.ts")
+            .contains("@/schemas/money"));
+    }
+
+    #[test]
+    fn referenced_types_are_imported_from_their_files() {
+        let project = Project::with_files(&[
+            ("Enums/Status.cs", "namespace App.Enums; public enum Status { Active, Inactive }"),
+            (
+                "Students/StudentDto.cs",
+                "namespace App.Students; public record StudentDto(int Id, App.Enums.Status Status); public record PagedResult<T>(List<T> Items, int Total);",
+            ),
+            (
+                "Contracts/Roster.cs",
+                r#"
+                using App.Enums;
+                using App.Students;
+
+                namespace App.Contracts;
+
+                public interface IEntity { int Id { get; } }
+                public interface IRoster : IEntity { StudentDto Lead { get; } Status Status { get; } PagedResult<StudentDto> Page { get; } }
+                public record RosterDto(StudentDto Lead, List<Status> Statuses);
+                "#,
+            ),
+        ]);
+
+        project.generate_all(&Config::default());
+        let roster = project.output("Contracts/Roster.schema.ts");
+        assert!(roster.contains(
+            "import { StudentDtoSchema, type StudentDto, type PagedResult } from '../Students/StudentDto.schema';\nimport { Status } from '../Enums/Status';\n"
+        ));
+        assert!(roster
+            .contains("  Lead: StudentDto;\n  Status: Status;\n  Page: PagedResult<StudentDto>;"));
+        assert!(project
+            .output("Students/StudentDto.schema.ts")
+            .contains("import { Status } from '../Enums/Status';\n"));
+
+        project.generate_all(&Config {
+            import_alias: Some("@/generated".to_string()),
+            ..Config::default()
+        });
+        let roster = project.output("Contracts/Roster.schema.ts");
+        assert!(roster.contains("} from '@/generated/Students/StudentDto.schema';\n"));
+        assert!(roster.contains("import { Status } from '@/generated/Enums/Status';\n"));
+    }
+
+    #[test]
+    fn enum_properties_use_native_or_union_enums() {
+        let source = r#"
+            public enum Status { [Display(Name = "Is active")] Active, Inactive }
+            [Flags] public enum Access { None = 0, Read = 1, Write = 2 }
+            public record StudentDto(Status Status, List<Status> History, Access Access, Status Fallback = Status.Inactive);
+            "#;
+        let project = Project::with_files(&[("Students.cs", source)]);
+
+        project.generate_all(&Config::default());
+        let schemas = project.output("Students.schema.ts");
+        assert!(schemas.contains("import { Status, AccessSchema } from './Students';\n"));
+        for line in [
+            "    Status: z.nativeEnum(Status)",
+            "    History: z.array(z.nativeEnum(Status))",
+            "    Access: AccessSchema",
+            "    Fallback: z.nativeEnum(Status).default(Status.Inactive),",
+        ] {
+            assert!(schemas.contains(line), "missing {line}:\n{schemas}");
+        }
+
+        project.generate_all(&Config {
+            enum_style: EnumStyle::Union,
+            ..Config::default()
+        });
+        let enums = project.output("Students.ts");
+        assert!(enums.contains("export type Status =\n  | 'Is active'\n  | 'Inactive';"));
+        let schemas = project.output("Students.schema.ts");
+        assert!(schemas.contains("import { AccessSchema } from './Students';\n"));
+        for line in [
+            "    Status: z.enum(['Is active', 'Inactive'])",
+            "    History: z.array(z.enum(['Is active', 'Inactive']))",
+            "    Fallback: z.enum(['Is active', 'Inactive']).default('Inactive'),",
+        ] {
+            assert!(schemas.contains(line), "missing {line}:\n{schemas}");
+        }
+    }
+
+    #[test]
+    fn self_referencing_schemas_are_lazy_and_typed() {
+        let project = Project::with_files(&[(
+            "Node.cs",
+            r#"
+            #nullable enable
+            public class Node
+            {
+                public string Name { get; set; } = "root";
+                public Node? Next { get; set; }
+                public List<Node> Children { get; set; } = new();
+            }
+            "#,
+        )]);
+        project.generate_all(&Config::default());
+        let schemas = project.output("Node.schema.ts");
+        assert!(schemas.contains(
+            "export interface Node {\n  Name: string;\n  Next: Node | null;\n  Children: Node[];\n}"
+        ));
+        assert!(schemas.contains(
+            "export const NodeSchema: z.ZodType<Node, z.ZodTypeDef, unknown> = z.object({\n"
+        ));
+        assert!(schemas.contains("    Next: z.lazy(() => NodeSchema).nullable()"));
+        assert!(schemas.contains("    Children: z.array(z.lazy(() => NodeSchema)).default([]),\n"));
+        assert!(!schemas.contains("z.infer"));
+    }
+
+    #[test]
+    fn mutually_recursive_schemas_across_files() {
+        let project = Project::with_files(&[
+            (
+                "Course.cs",
+                r#"
+                #nullable enable
+                namespace App;
+                public class Course
+                {
+                    public int Id { get; set; }
+                    public List<Student> Students { get; set; }
+                }
+                "#,
+            ),
+            (
+                "Student.cs",
+                r#"
+                #nullable enable
+                namespace App;
+                public class Student
+                {
+                    public Course? Course { get; set; }
+                    public Grade Grade { get; set; }
+                }
+                public class Grade
+                {
+                    public int Value { get; set; }
+                }
+                "#,
+            ),
+        ]);
+        project.generate_all(&Config::default());
+
+        let course = project.output("Course.schema.ts");
+        assert!(course.contains("import { StudentSchema, type Student } from './Student.schema';"));
+        assert!(
+            course.contains("export interface Course {\n  Id: number;\n  Students: Student[];\n}")
+        );
+        assert!(course.contains("export const CourseSchema: z.ZodType<Course> = z.object({\n"));
+        assert!(course.contains("    Students: z.array(z.lazy(() => StudentSchema))"));
+
+        let student = project.output("Student.schema.ts");
+        assert!(student.contains("    Course: z.lazy(() => CourseSchema).nullable()"));
+        // Schemas outside the cycle are referenced directly, and declared first
+        assert!(student.contains("    Grade: GradeSchema"));
+        assert!(student.find("GradeSchema = ").unwrap() < student.find("StudentSchema: ").unwrap());
+        assert!(student.contains("export type Grade = z.infer<typeof GradeSchema>;"));
+    }
+
+    #[test]
+    fn precision_applies_per_type_and_property() {
+        let config: Config = toml::from_str(
+            r#"
+            [precision]
+            long = "coerced_bigint"
+            decimal = "string"
+
+            [precision.properties]
+            "GradeDto.Average" = "number"
+            "#,
+        )
+        .unwrap();
+        let project = Project::with_files(&[(
+            "Grades.cs",
+            r#"
+            public record GradeDto(long Id, ulong Big, decimal Average, decimal Total, List<decimal> Scores);
+            public interface IGrade { long Id { get; } decimal Total { get; } }
+            "#,
+        )]);
+        project.generate_all(&config);
+        let schemas = project.output("Grades.schema.ts");
+
+        for line in [
+            "    Id: z.coerce.bigint().min(-9223372036854775808n).max(9223372036854775807n)",
+            "    Big: z.coerce.bigint().min(0n).max(18446744073709551615n)",
+            "    Average: z.number()",
+            "    Total: z.string().regex(/^-?\\d+(\\.\\d+)?$/)",
+            "    Scores: z.array(z.string().regex(/^-?\\d+(\\.\\d+)?$/))",
+        ] {
+            assert!(schemas.contains(line), "missing {line}:\n{schemas}");
+        }
+        assert!(schemas.contains("export interface IGrade {\n  Id: bigint;\n  Total: string;\n}"));
+    }
+
+    #[test]
+    fn nullable_context_comes_from_project_files_and_directives() {
+        let project = Project::with_files(&[
+            (
+                "Modern/Modern.csproj",
+                "<Project><PropertyGroup><Nullable>enable</Nullable></PropertyGroup></Project>",
+            ),
+            (
+                "Modern/StudentDto.cs",
+                r#"
+                public record StudentDto(string Name, string? Nickname, int Age)
+                {
+                #nullable disable
+                    public string Legacy { get; set; }
+                #nullable restore
+                    public string Email { get; set; }
+                }
+                "#,
+            ),
+            (
+                "Legacy/Directory.Build.props",
+                "<Project><PropertyGroup><Nullable>disable</Nullable></PropertyGroup></Project>",
+            ),
+            (
+                "Unset/PlainDto.cs",
+                "public record PlainDto(string Name, string? Nickname);",
+            ),
+            (
+                "Legacy/OldDto.cs",
+                r#"
+                public class OldDto
+                {
+                    public string Name { get; set; }
+                    public int Age { get; set; }
+                #nullable enable
+                    public string Required { get; set; }
+                }
+                "#,
+            ),
+        ]);
+        project.generate_all(&Config::default());
+
+        let modern = project.output("Modern/StudentDto.schema.ts");
+        for line in [
+            "    Name: z.string(),",
+            "    Nickname: z.string().nullable(),",
+            "    Age: z.number().int(),",
+            "    Legacy: z.string().nullable(),",
+            "    Email: z.string(),",
+        ] {
+            assert!(modern.contains(line), "missing {line}:\n{modern}");
+        }
+        let legacy = project.output("Legacy/OldDto.schema.ts");
+        for line in [
+            "    Name: z.string().nullable(),",
+            "    Age: z.number().int(),",
+            "    Required: z.string(),",
+        ] {
+            assert!(legacy.contains(line), "missing {line}:\n{legacy}");
+        }
+        // Without a project setting, unannotated references stay non-nullable
+        let plain = project.output("Unset/PlainDto.schema.ts");
+        assert!(plain.contains("    Name: z.string(),\n    Nickname: z.string().nullable(),\n"));
+    }
+
+    #[test]
+    fn wrapped_struct_values_are_never_nullable() {
+        let project = Project::with_files(&[
+            (
+                "Directory.Build.props",
+                "<Project><PropertyGroup><Nullable>disable</Nullable></PropertyGroup></Project>",
+            ),
+            (
+                "Ids.cs",
+                r#"
+                public readonly record struct Email([EmailAddress] string Value);
+                public record StudentDto(Email Email, string Name);
+                "#,
+            ),
+        ]);
+        let cases = [
+            (
+                WrapperStructStyle::Unwrap,
+                "export const EmailSchema = z.string().email({ message: 'Invalid email address' });\n",
+            ),
+            (
+                WrapperStructStyle::Brand,
+                "export const EmailSchema = z.string().email({ message: 'Invalid email address' }).brand<'Email'>();\n",
+            ),
+        ];
+        for (wrapper_structs, expected) in cases {
+            project.generate_all(&Config {
+                wrapper_structs,
+                ..Config::default()
+            });
+            let schemas = project.output("Ids.schema.ts");
+            assert!(
+                schemas.contains(expected),
+                "{wrapper_structs:?}:\n{schemas}"
+            );
+            assert!(schemas.contains("    Name: z.string().nullable(),\n"));
+        }
+    }
+
+    fn namespace_layout() -> Config {
+        Config {
+            output_layout: OutputLayout::Namespace,
+            namespace_prefix: Some("App".to_string()),
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn same_named_files_in_one_namespace_get_distinct_outputs() {
+        let project = Project::with_files(&[
+            (
+                "Students/Dtos.cs",
+                "namespace App.Dtos; public class StudentDto { public int Id { get; set; } }",
+            ),
+            (
+                "Teachers/Dtos.cs",
+                "namespace App.Dtos; public class TeacherDto { public int Id { get; set; } }",
+            ),
+        ]);
+        project.generate_all(&namespace_layout());
+        assert!(!project.exists("dtos/Dtos.schema.ts"));
+        assert!(project
+            .output("dtos/Students.Dtos.schema.ts")
+            .contains("StudentDtoSchema"));
+        assert!(project
+            .output("dtos/Teachers.Dtos.schema.ts")
+            .contains("TeacherDtoSchema"));
+    }
+
+    #[test]
+    fn files_spanning_namespaces_are_split() {
+        let project = Project::with_files(&[(
+            "Mixed.cs",
+            r#"
+            namespace App.Students
+            {
+                public class StudentDto { public int Id { get; set; } }
+            }
+
+            namespace App.Teachers
+            {
+                using App.Students;
+
+                public class TeacherDto { public List<StudentDto> Students { get; set; } }
+            }
+            "#,
+        )]);
+        project.generate_all(&namespace_layout());
+        let students = project.output("students/Mixed.schema.ts");
+        assert!(students.contains("StudentDtoSchema"));
+        assert!(!students.contains("TeacherDto"));
+        let teachers = project.output("teachers/Mixed.schema.ts");
+        assert!(teachers.contains("import { StudentDtoSchema } from '../students/Mixed.schema';"));
+        assert!(teachers.contains("    Students: z.array(StudentDtoSchema)"));
+    }
+
+    #[test]
+    fn inheritdoc_resolves_base_types_in_other_files() {
+        let project = Project::with_files(&[
+            (
+                "TreeBase.cs",
+                r#"
+                /// <summary>A node of the tree</summary>
+                public class TreeBase
+                {
+                    /// <summary>Display label</summary>
+                    public string Label { get; set; }
+                }
+                "#,
+            ),
+            (
+                "Derived.cs",
+                r#"
+                /// <inheritdoc/>
+                public class Derived : TreeBase
+                {
+                    /// <inheritdoc/>
+                    public new string Label { get; set; }
+                }
+                "#,
+            ),
+        ]);
+        project.generate_all(&Config::default());
+        let schemas = project.output("Derived.schema.ts");
+        assert!(schemas.contains("/**\n * A node of the tree\n */\nexport const DerivedSchema"));
+        assert!(schemas.contains("    /** Display label */\n    Label:"));
+    }
+
+    #[test]
+    fn interpolated_strings_are_not_defaults() {
+        let project = Project::with_files(&[(
+            "Holder.cs",
+            r#"
+            public class Holder
+            {
+                public string Computed { get; set; } = $"a{1 + 2}b";
+                public string Named { get; set; } = $"{nameof(Holder)}-x";
+                public string Literal { get; set; } = "{not a hole}";
+            }
+            "#,
+        )]);
+        project.generate_all(&Config::default());
+        let schemas = project.output("Holder.schema.ts");
+        assert!(schemas.contains("    Computed: z.string(),\n"));
+        assert!(schemas.contains("    Named: z.string(),\n"));
+        assert!(schemas.contains("    Literal: z.string().default('{not a hole}'),\n"));
+    }
+
+    #[test]
+    fn same_named_types_resolve_through_namespaces_and_usings() {
+        let project = Project::with_files(&[
+            (
+                "Grades/Status.cs",
+                "namespace App.Grades; public enum Status { Active, Inactive }",
+            ),
+            (
+                "Billing/Status.cs",
+                "namespace App.Billing; [Flags] public enum Status { None = 0, Paid = 1, Overdue = 2 }",
+            ),
+            (
+                "Grades/StudentDto.cs",
+                r#"
+                namespace App.Grades;
+
+                public class StudentDto
+                {
+                    public Status Status { get; set; } = Status.Active;
+                    public Billing.Status Billing { get; set; }
+                }
+                "#,
+            ),
+            (
+                "Reports/InvoiceDto.cs",
+                r#"
+                using App.Billing;
+
+                namespace App.Reports;
+
+                public class InvoiceDto
+                {
+                    public Status Status { get; set; }
+                }
+                "#,
+            ),
+        ]);
+        project.generate_all(&Config::default());
+
+        let student = project.output("Grades/StudentDto.schema.ts");
+        assert!(student.contains("import { Status } from './Status';"));
+        assert!(student.contains("import { StatusSchema } from '../Billing/Status';"));
+        assert!(student.contains("    Status: z.nativeEnum(Status).default(Status.Active),\n"));
+        assert!(student.contains("    Billing: StatusSchema,\n"));
+
+        let invoice = project.output("Reports/InvoiceDto.schema.ts");
+        assert!(invoice.contains("import { StatusSchema } from '../Billing/Status';"));
+        assert!(invoice.contains("    Status: StatusSchema,\n"));
+    }
+
+    #[test]
+    fn watch_mode_regenerates_importers_of_changed_types() {
+        let project = Project::with_files(&[
+            (
+                "Status.cs",
+                "namespace App; public enum Status { None = 0, Paid = 1 }",
+            ),
+            (
+                "InvoiceDto.cs",
+                "namespace App; public class InvoiceDto { public Status Status { get; set; } }",
+            ),
+        ]);
+        let config = Config::default();
+        let mut processor = FileProcessor::new();
+        project.generate(&mut processor, &config);
+        assert!(project
+            .output("InvoiceDto.schema.ts")
+            .contains("    Status: z.nativeEnum(Status),\n"));
+
+        project.write(
+            "Status.cs",
+            "namespace App; [Flags] public enum Status { None = 0, Paid = 1 }",
+        );
+        project.generate(&mut processor, &config);
+        let invoice = project.output("InvoiceDto.schema.ts");
+        assert!(invoice.contains("import { StatusSchema } from './Status';"));
+        assert!(invoice.contains("    Status: StatusSchema,\n"));
+
+        fs::rename(
+            project.root.join("cs/Status.cs"),
+            project.root.join("cs/Enums.cs"),
+        )
+        .unwrap();
+        project.generate(&mut processor, &config);
+        assert!(!project.exists("Status.ts"));
+        assert!(project.exists("Enums.ts"));
+        assert!(project
+            .output("InvoiceDto.schema.ts")
+            .contains("import { StatusSchema } from './Enums';"));
+
+        project.generate(&mut processor, &config);
+        assert_eq!(processor.stats.files_skipped, 2);
+    }
+
+    #[test]
+    fn recursive_bases_are_extended_through_their_object_schema() {
+        let project = Project::with_files(&[
+            (
+                "TreeBase.cs",
+                r#"
+                #nullable enable
+                public class TreeBase
+                {
+                    public List<TreeBase> Kids { get; set; }
+                }
+                "#,
+            ),
+            (
+                "Derived.cs",
+                r#"
+                #nullable enable
+                public class Derived : TreeBase
+                {
+                    public int Depth { get; set; }
+                }
+                "#,
+            ),
+        ]);
+        project.generate_all(&Config::default());
+
+        let base = project.output("TreeBase.schema.ts");
+        assert!(base.contains("export const TreeBaseObjectSchema = z.object({\n"));
+        assert!(base.contains("    Kids: z.array(z.lazy(() => TreeBaseSchema)),\n"));
+        assert!(base.contains(
+            "export const TreeBaseSchema: z.ZodType<TreeBase> = TreeBaseObjectSchema;\n"
+        ));
+
+        let derived = project.output("Derived.schema.ts");
+        assert!(derived.contains("import { TreeBaseObjectSchema } from './TreeBase.schema';"));
+        assert!(derived.contains("export const DerivedSchema = TreeBaseObjectSchema.extend({\n"));
+        assert!(!derived.contains("TreeBaseSchema"));
+    }
+
+    #[test]
+    fn precision_applies_to_type_arguments() {
+        let project = Project::with_files(&[(
+            "Responses.cs",
+            r#"
+            #nullable enable
+            public class ApiResponse<T>
+            {
+                public T Data { get; set; }
+            }
+            public class Totals
+            {
+                public ApiResponse<long> Count { get; set; }
+                public Dictionary<string, ApiResponse<decimal?>> Sums { get; set; }
+            }
+            "#,
+        )]);
+        let mut config = Config::default();
+        config.precision.long = Precision::Bigint;
+        config.precision.decimal = Precision::String;
+        project.generate_all(&config);
+        let schemas = project.output("Responses.schema.ts");
+        assert!(schemas.contains(
+            "    Count: ApiResponseSchema(z.bigint().min(-9223372036854775808n).max(9223372036854775807n)),\n"
+        ));
+        assert!(schemas.contains(
+            "    Sums: z.record(z.string(), ApiResponseSchema(z.string().regex(/^-?\\d+(\\.\\d+)?$/).nullable())),\n"
+        ));
+    }
+
+    #[test]
+    fn validations_come_before_nullable_and_optional() {
+        let project = Project::with_files(&[(
+            "Students.cs",
+            r#"
+            #nullable enable
+            public class StudentDto
+            {
+                [StringLength(50, MinimumLength = 2)]
+                public string? Name { get; set; } = "New";
+
+                [Range(1, 12)]
+                public int? Grade { get; set; }
+            }
+            public class UpdateStudentDto
+            {
+                [EmailAddress]
+                public string? Email { get; set; }
+            }
+            public readonly record struct Email([EmailAddress] string Value);
+            "#,
+        )]);
+        project.generate_all(&Config {
+            wrapper_structs: WrapperStructStyle::Brand,
+            ..Config::default()
+        });
+        let schemas = project.output("Students.schema.ts");
+        assert!(schemas.contains(
+            "    Name: z.string().min(2, { message: 'Minimum length is 2' }).max(50, { message: 'Maximum length is 50' }).nullable().default('New'),\n"
+        ));
+        assert!(schemas.contains(
+            "    Grade: z.number().int().min(1, { message: 'Value must be between 1 and 12' }).max(12, { message: 'Value must be between 1 and 12' }).nullable(),\n"
+        ));
+        assert!(schemas.contains(
+            "    Email: z.string().email({ message: 'Invalid email address' }).nullable().optional(),\n"
+        ));
+        assert!(schemas.contains(
+            "export const EmailSchema = z.string().email({ message: 'Invalid email address' }).brand<'Email'>();\n"
+        ));
+    }
+
+    #[test]
+    fn generic_dtos_declare_typescript_generic_interfaces() {
+        let project = Project::with_files(&[
+            (
+                "PagedResult.cs",
+                "public record PagedResult<T>(List<T> Items, int Total);",
+            ),
+            (
+                "Roster.cs",
+                r#"
+                public record StudentDto(string Name);
+                public interface IRoster { PagedResult<StudentDto> Page { get; } }
+                public class Node
+                {
+                    public string Name { get; set; }
+                    public PagedResult<Node> Children { get; set; }
+                }
+                "#,
+            ),
+        ]);
+        project.generate_all(&Config::default());
+
+        let paged = project.output("PagedResult.schema.ts");
+        assert!(
+            paged.contains("export interface PagedResult<T> {\n  Items: T[];\n  Total: number;\n}")
+        );
+        assert!(
+            paged.contains("export const PagedResultSchema = <T extends z.ZodTypeAny>(item: T) =>")
+        );
+        assert!(!paged.contains("export type PagedResult"));
+
+        let roster = project.output("Roster.schema.ts");
+        assert!(roster.contains(
+            "import { PagedResultSchema, type PagedResult } from './PagedResult.schema';"
+        ));
+        assert!(roster.contains("export interface IRoster {\n  Page: PagedResult<StudentDto>;\n}"));
+        assert!(roster.contains(
+            "export interface Node {\n  Name: string;\n  Children: PagedResult<Node>;\n}"
+        ));
+        assert!(roster.contains("    Children: PagedResultSchema(z.lazy(() => NodeSchema)),\n"));
+    }
+
+    #[test]
+    fn inheritance_cycles_follow_the_ordered_schemas() {
+        let project = Project::with_files(&[(
+            "Cycle.cs",
+            r#"
+            public class A : B { public int X { get; set; } }
+            public class B : A { public int Y { get; set; } }
+            public class Child : Parent { public int Z { get; set; } }
+            public class Parent { public int W { get; set; } }
+            "#,
+        )]);
+        project.generate_all(&Config::default());
+        let schemas = project.output("Cycle.schema.ts");
+        let position = |name: &str| {
+            schemas
+                .find(&format!("export const {} = ", name))
+                .unwrap_or_else(|| panic!("{} was not generated", name))
+        };
+        assert!(position("ParentSchema") < position("ChildSchema"));
+        assert!(position("ChildSchema") < position("AObjectSchema"));
+        assert!(position("AObjectSchema") < position("BObjectSchema"));
     }
 }
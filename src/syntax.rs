@@ -0,0 +1,1219 @@
+use crate::lexer::{Trivia, TriviaKind};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+#[derive(Debug, Default)]
+pub struct CompilationUnit {
+    pub usings: Vec<UsingDirective>,
+    pub members: Vec<NamespaceMember>,
+}
+
+#[derive(Debug, Clone)]
+pub struct UsingDirective {
+    pub is_global: bool,
+    pub alias: Option<String>,
+    pub target: TypeSyntax,
+}
+
+#[derive(Debug)]
+pub enum NamespaceMember {
+    Namespace(NamespaceDecl),
+    Type(TypeDecl),
+}
+
+#[derive(Debug)]
+pub struct NamespaceDecl {
+    pub name: String,
+    pub file_scoped: bool,
+    pub usings: Vec<UsingDirective>,
+    pub members: Vec<NamespaceMember>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypeKind {
+    Class,
+    Struct,
+    Interface,
+    Enum,
+    Record,
+    RecordStruct,
+}
+
+#[derive(Debug)]
+pub struct TypeDecl {
+    pub kind: TypeKind,
+    pub name: String,
+    /// Name qualified with its namespace and enclosing types, once
+    /// `resolve_type_references` has run
+    pub full_name: String,
+    pub type_parameters: Vec<String>,
+    pub modifiers: Vec<String>,
+    pub attributes: Vec<Attribute>,
+    pub trivia: Vec<Trivia>,
+    /// Primary constructor or positional record parameters
+    pub parameters: Option<Vec<Parameter>>,
+    pub base_types: Vec<TypeSyntax>,
+    pub members: Vec<MemberDecl>,
+}
+
+#[derive(Debug)]
+pub enum MemberDecl {
+    Property(PropertyDecl),
+    Field(FieldDecl),
+    EnumMember(EnumMemberDecl),
+    Type(TypeDecl),
+}
+
+#[derive(Debug)]
+pub struct Parameter {
+    pub name: String,
+    pub type_name: TypeSyntax,
+    pub attributes: Vec<Attribute>,
+    pub trivia: Vec<Trivia>,
+    pub default_value: Option<Expr>,
+    /// Whether `?` annotations are enabled where the type is written
+    pub nullable_annotations: Option<bool>,
+}
+
+#[derive(Debug)]
+pub struct PropertyDecl {
+    pub name: String,
+    pub type_name: TypeSyntax,
+    pub modifiers: Vec<String>,
+    pub attributes: Vec<Attribute>,
+    pub trivia: Vec<Trivia>,
+    pub accessors: Vec<String>,
+    pub initializer: Option<Expr>,
+    /// Whether `?` annotations are enabled where the type is written
+    pub nullable_annotations: Option<bool>,
+}
+
+#[derive(Debug)]
+pub struct FieldDecl {
+    pub name: String,
+    pub type_name: TypeSyntax,
+    pub modifiers: Vec<String>,
+    pub attributes: Vec<Attribute>,
+    pub trivia: Vec<Trivia>,
+    pub initializer: Option<Expr>,
+}
+
+#[derive(Debug)]
+pub struct EnumMemberDecl {
+    pub name: String,
+    pub attributes: Vec<Attribute>,
+    pub trivia: Vec<Trivia>,
+    pub value: Option<Expr>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypeSyntax {
+    /// A possibly qualified, possibly generic name; `global::` is dropped
+    Named {
+        qualifier: Vec<String>,
+        name: String,
+        arguments: Vec<TypeSyntax>,
+    },
+    /// Element type and rank, so `T[,]` has rank 2
+    Array(Box<TypeSyntax>, usize),
+    Nullable(Box<TypeSyntax>),
+    Pointer(Box<TypeSyntax>),
+    Tuple(Vec<TupleElement>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TupleElement {
+    pub type_name: TypeSyntax,
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Attribute {
+    pub name: String,
+    pub arguments: Vec<AttributeArgument>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AttributeArgument {
+    pub name: Option<String>,
+    pub value: Expr,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    String(String),
+    Char(String),
+    Number(String),
+    Bool(bool),
+    Null,
+    /// Simple or member-access name such as `Status.Active`
+    Name(String),
+    Unary(String, Box<Expr>),
+    Binary(Box<Expr>, String, Box<Expr>),
+    Cast(TypeSyntax, Box<Expr>),
+    /// `new T(...)`, or target-typed `new(...)` without a type
+    New(Option<TypeSyntax>, Vec<Expr>),
+    /// Collection expression `[a, b]` or array initializer `new[] { a, b }`
+    Collection(Vec<Expr>),
+    /// Anything the parser does not model, kept as source text
+    Raw(String),
+}
+
+impl Attribute {
+    /// Matches both `Display` and `DisplayAttribute`, with or without namespace
+    pub fn is(&self, name: &str) -> bool {
+        let short = self.name.rsplit('.').next().unwrap_or(&self.name);
+        short == name || short.strip_suffix("Attribute") == Some(name)
+    }
+
+    pub fn positional(&self, index: usize) -> Option<&Expr> {
+        self.arguments
+            .iter()
+            .filter(|arg| arg.name.is_none())
+            .nth(index)
+            .map(|arg| &arg.value)
+    }
+
+    pub fn named(&self, name: &str) -> Option<&Expr> {
+        self.arguments
+            .iter()
+            .find(|arg| arg.name.as_deref() == Some(name))
+            .map(|arg| &arg.value)
+    }
+}
+
+impl fmt::Display for TypeSyntax {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TypeSyntax::Named {
+                qualifier,
+                name,
+                arguments,
+            } => {
+                for segment in qualifier {
+                    write!(f, "{}.", segment)?;
+                }
+                write!(f, "{}", name)?;
+                if !arguments.is_empty() {
+                    let arguments: Vec<String> = arguments.iter().map(|a| a.to_string()).collect();
+                    write!(f, "<{}>", arguments.join(", "))?;
+                }
+                Ok(())
+            }
+            TypeSyntax::Array(element, rank) => {
+                write!(f, "{}[{}]", element, ",".repeat(rank - 1))
+            }
+            TypeSyntax::Nullable(inner) => write!(f, "{}?", inner),
+            TypeSyntax::Pointer(inner) => write!(f, "{}*", inner),
+            TypeSyntax::Tuple(elements) => {
+                let elements: Vec<String> = elements
+                    .iter()
+                    .map(|e| match &e.name {
+                        Some(name) => format!("{} {}", e.type_name, name),
+                        None => e.type_name.to_string(),
+                    })
+                    .collect();
+                write!(f, "({})", elements.join(", "))
+            }
+        }
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expr::String(s) => write!(f, "{:?}", s),
+            Expr::Char(c) => write!(f, "'{}'", c),
+            Expr::Number(n) | Expr::Name(n) | Expr::Raw(n) => write!(f, "{}", n),
+            Expr::Bool(b) => write!(f, "{}", b),
+            Expr::Null => write!(f, "null"),
+            Expr::Unary(op, operand) => write!(f, "{}{}", op, Parenthesized(operand)),
+            Expr::Binary(left, op, right) => {
+                write!(f, "{} {} {}", Parenthesized(left), op, Parenthesized(right))
+            }
+            Expr::Cast(type_name, operand) => {
+                write!(f, "({}){}", type_name, Parenthesized(operand))
+            }
+            Expr::New(type_name, arguments) => {
+                let arguments: Vec<String> = arguments.iter().map(|a| a.to_string()).collect();
+                match type_name {
+                    Some(type_name) => write!(f, "new {}({})", type_name, arguments.join(", ")),
+                    None => write!(f, "new({})", arguments.join(", ")),
+                }
+            }
+            Expr::Collection(elements) => {
+                let elements: Vec<String> = elements.iter().map(|e| e.to_string()).collect();
+                write!(f, "[{}]", elements.join(", "))
+            }
+        }
+    }
+}
+
+/// Wraps compound operands so the printed expression keeps its grouping
+struct Parenthesized<'a>(&'a Expr);
+
+impl fmt::Display for Parenthesized<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Expr::Binary(..) | Expr::Cast(..) => write!(f, "({})", self.0),
+            expr => write!(f, "{}", expr),
+        }
+    }
+}
+
+impl Expr {
+    /// Literal value as it would appear in generated TypeScript source
+    pub fn as_text(&self) -> String {
+        match self {
+            Expr::String(s) | Expr::Char(s) => s.clone(),
+            Expr::Bool(b) => b.to_string(),
+            Expr::Null => "null".to_string(),
+            expr => expr.to_string(),
+        }
+    }
+
+    /// Evaluates an integral constant expression; `lookup` resolves names
+    /// such as other enum members
+    pub fn evaluate(&self, lookup: &dyn Fn(&str) -> Option<i128>) -> Option<i128> {
+        match self {
+            Expr::Number(n) => parse_integer_literal(n),
+            Expr::Char(c) => c.chars().next().map(|c| c as i128),
+            Expr::Name(name) => lookup(name),
+            Expr::Cast(_, operand) => operand.evaluate(lookup),
+            Expr::Unary(op, operand) => {
+                let value = operand.evaluate(lookup)?;
+                match op.as_str() {
+                    "-" => Some(-value),
+                    "+" => Some(value),
+                    "~" => Some(!value),
+                    _ => None,
+                }
+            }
+            Expr::Binary(left, op, right) => {
+                let left = left.evaluate(lookup)?;
+                let right = right.evaluate(lookup)?;
+                match op.as_str() {
+                    "|" => Some(left | right),
+                    "&" => Some(left & right),
+                    "^" => Some(left ^ right),
+                    "<<" => left.checked_shl(u32::try_from(right).ok()?),
+                    ">>" => left.checked_shr(u32::try_from(right).ok()?),
+                    "+" => left.checked_add(right),
+                    "-" => left.checked_sub(right),
+                    "*" => left.checked_mul(right),
+                    "/" => left.checked_div(right),
+                    "%" => left.checked_rem(right),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Parses decimal, hex and binary integer literals with digit separators and
+/// `u`/`l` suffixes
+pub fn parse_integer_literal(text: &str) -> Option<i128> {
+    let text = text
+        .replace('_', "")
+        .trim_end_matches(['u', 'U', 'l', 'L'])
+        .to_string();
+    if let Some(hex) = text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        i128::from_str_radix(hex, 16).ok()
+    } else if let Some(binary) = text.strip_prefix("0b").or_else(|| text.strip_prefix("0B")) {
+        i128::from_str_radix(binary, 2).ok()
+    } else {
+        text.parse().ok()
+    }
+}
+
+impl TypeDecl {
+    pub fn has_modifier(&self, modifier: &str) -> bool {
+        self.modifiers.iter().any(|m| m == modifier)
+    }
+
+    pub fn attribute(&self, name: &str) -> Option<&Attribute> {
+        self.attributes.iter().find(|a| a.is(name))
+    }
+
+    pub fn doc_comment(&self) -> Option<String> {
+        doc_comment(&self.trivia)
+    }
+}
+
+impl FieldDecl {
+    pub fn has_modifier(&self, modifier: &str) -> bool {
+        self.modifiers.iter().any(|m| m == modifier)
+    }
+}
+
+impl PropertyDecl {
+    pub fn has_modifier(&self, modifier: &str) -> bool {
+        self.modifiers.iter().any(|m| m == modifier)
+    }
+
+    pub fn is_public_instance(&self) -> bool {
+        self.has_modifier("public") && !self.has_modifier("static")
+    }
+
+    pub fn is_readable(&self) -> bool {
+        self.accessors.iter().any(|a| a == "get")
+    }
+}
+
+/// Joins the `///` lines immediately preceding a declaration
+pub fn doc_comment(trivia: &[Trivia]) -> Option<String> {
+    let lines: Vec<&str> = trivia
+        .iter()
+        .filter(|t| t.kind == TriviaKind::DocComment)
+        .map(|t| t.text.as_str())
+        .collect();
+    if lines.is_empty() {
+        None
+    } else {
+        Some(lines.join("\n"))
+    }
+}
+
+impl CompilationUnit {
+    /// All type declarations in the file, including nested ones
+    pub fn types(&self) -> Vec<&TypeDecl> {
+        let mut types = Vec::new();
+        collect_namespace_types(&self.members, &mut types);
+        types
+    }
+
+    /// Full name of the namespace holding the file's first type declaration
+    pub fn namespace(&self) -> Option<String> {
+        first_type_namespace(&self.members, None)
+    }
+
+    /// Splits the file into one unit per namespace declaring types, each
+    /// keeping the usings and namespace declarations around its types
+    pub fn split_by_namespace(self) -> Vec<CompilationUnit> {
+        let mut types = Vec::new();
+        collect_scoped_types(self.members, &mut Vec::new(), &mut types);
+
+        let mut parts: Vec<(Vec<String>, CompilationUnit)> = Vec::new();
+        for (scopes, decl) in types {
+            let path: Vec<String> = scopes.iter().map(|ns| ns.name.clone()).collect();
+            let index = match parts.iter().position(|(p, _)| *p == path) {
+                Some(index) => index,
+                None => {
+                    parts.push((
+                        path,
+                        CompilationUnit {
+                            usings: self.usings.clone(),
+                            members: Vec::new(),
+                        },
+                    ));
+                    parts.len() - 1
+                }
+            };
+            let mut members = &mut parts[index].1.members;
+            for scope in scopes {
+                let position = members.iter().position(
+                    |m| matches!(m, NamespaceMember::Namespace(ns) if ns.name == scope.name),
+                );
+                let position = position.unwrap_or_else(|| {
+                    members.push(NamespaceMember::Namespace(scope));
+                    members.len() - 1
+                });
+                members = match &mut members[position] {
+                    NamespaceMember::Namespace(ns) => &mut ns.members,
+                    NamespaceMember::Type(_) => unreachable!(),
+                };
+            }
+            members.push(NamespaceMember::Type(decl));
+        }
+
+        if parts.is_empty() {
+            return vec![CompilationUnit {
+                usings: self.usings,
+                members: Vec::new(),
+            }];
+        }
+        parts.into_iter().map(|(_, unit)| unit).collect()
+    }
+}
+
+/// Top-level type declarations with the namespaces enclosing them, the
+/// latter copied without their members
+fn collect_scoped_types(
+    members: Vec<NamespaceMember>,
+    scopes: &mut Vec<NamespaceDecl>,
+    types: &mut Vec<(Vec<NamespaceDecl>, TypeDecl)>,
+) {
+    for member in members {
+        match member {
+            NamespaceMember::Namespace(mut ns) => {
+                let members = std::mem::take(&mut ns.members);
+                scopes.push(ns);
+                collect_scoped_types(members, scopes, types);
+                scopes.pop();
+            }
+            NamespaceMember::Type(decl) => {
+                types.push((scopes.iter().map(NamespaceDecl::shell).collect(), decl))
+            }
+        }
+    }
+}
+
+impl NamespaceDecl {
+    /// Copy of the declaration without its members
+    fn shell(&self) -> NamespaceDecl {
+        NamespaceDecl {
+            name: self.name.clone(),
+            file_scoped: self.file_scoped,
+            usings: self.usings.clone(),
+            members: Vec::new(),
+        }
+    }
+}
+
+fn first_type_namespace(members: &[NamespaceMember], enclosing: Option<&str>) -> Option<String> {
+    for member in members {
+        match member {
+            NamespaceMember::Type(_) => return enclosing.map(str::to_string),
+            NamespaceMember::Namespace(ns) => {
+                let name = match enclosing {
+                    Some(outer) => format!("{}.{}", outer, ns.name),
+                    None => ns.name.clone(),
+                };
+                if let Some(found) = first_type_namespace(&ns.members, Some(&name)) {
+                    return Some(found);
+                }
+            }
+        }
+    }
+    None
+}
+
+fn collect_namespace_types<'a>(members: &'a [NamespaceMember], types: &mut Vec<&'a TypeDecl>) {
+    for member in members {
+        match member {
+            NamespaceMember::Namespace(ns) => collect_namespace_types(&ns.members, types),
+            NamespaceMember::Type(decl) => collect_nested_types(decl, types),
+        }
+    }
+}
+
+fn collect_nested_types<'a>(decl: &'a TypeDecl, types: &mut Vec<&'a TypeDecl>) {
+    types.push(decl);
+    for member in &decl.members {
+        if let MemberDecl::Type(nested) = member {
+            collect_nested_types(nested, types);
+        }
+    }
+}
+
+/// Merges `partial` type declarations that share a fully qualified name into
+/// the first one seen, in unit order
+pub fn merge_partial_types(units: &mut [CompilationUnit]) {
+    let mut owners: HashMap<String, usize> = HashMap::new();
+    for (index, unit) in units.iter().enumerate() {
+        let mut names = Vec::new();
+        collect_partial_names(&unit.members, "", &mut names);
+        for name in names {
+            owners.entry(name).or_insert(index);
+        }
+    }
+
+    let mut parts = Vec::new();
+    for (index, unit) in units.iter_mut().enumerate() {
+        let mut seen = Vec::new();
+        take_partial_parts(&mut unit.members, "", &mut |name| {
+            let keep = owners[name] == index && !seen.iter().any(|s| s == name);
+            if keep {
+                seen.push(name.to_string());
+            }
+            keep
+        })
+        .into_iter()
+        .for_each(|(name, part)| parts.push((index, name, part)));
+    }
+
+    for (_, name, part) in parts {
+        if let Some(decl) = find_type_mut(&mut units[owners[&name]].members, "", &name) {
+            decl.absorb(part);
+        }
+    }
+}
+
+fn qualified_name(namespace: &str, decl: &TypeDecl) -> String {
+    let arity = if decl.type_parameters.is_empty() {
+        String::new()
+    } else {
+        format!("`{}", decl.type_parameters.len())
+    };
+    if namespace.is_empty() {
+        format!("{}{}", decl.name, arity)
+    } else {
+        format!("{}.{}{}", namespace, decl.name, arity)
+    }
+}
+
+fn nested_namespace(namespace: &str, ns: &NamespaceDecl) -> String {
+    if namespace.is_empty() {
+        ns.name.clone()
+    } else {
+        format!("{}.{}", namespace, ns.name)
+    }
+}
+
+fn collect_partial_names(members: &[NamespaceMember], namespace: &str, names: &mut Vec<String>) {
+    for member in members {
+        match member {
+            NamespaceMember::Namespace(ns) => {
+                collect_partial_names(&ns.members, &nested_namespace(namespace, ns), names)
+            }
+            NamespaceMember::Type(decl) if decl.has_modifier("partial") => {
+                names.push(qualified_name(namespace, decl))
+            }
+            NamespaceMember::Type(_) => {}
+        }
+    }
+}
+
+/// Removes the partial declarations `keep` rejects, returning them with
+/// their qualified names
+fn take_partial_parts(
+    members: &mut Vec<NamespaceMember>,
+    namespace: &str,
+    keep: &mut dyn FnMut(&str) -> bool,
+) -> Vec<(String, TypeDecl)> {
+    let mut taken = Vec::new();
+    let mut kept = Vec::new();
+    for member in members.drain(..) {
+        match member {
+            NamespaceMember::Namespace(mut ns) => {
+                let name = nested_namespace(namespace, &ns);
+                taken.extend(take_partial_parts(&mut ns.members, &name, keep));
+                kept.push(NamespaceMember::Namespace(ns));
+            }
+            NamespaceMember::Type(decl) if decl.has_modifier("partial") => {
+                let name = qualified_name(namespace, &decl);
+                if keep(&name) {
+                    kept.push(NamespaceMember::Type(decl));
+                } else {
+                    taken.push((name, decl));
+                }
+            }
+            member => kept.push(member),
+        }
+    }
+    *members = kept;
+    taken
+}
+
+fn find_type_mut<'a>(
+    members: &'a mut [NamespaceMember],
+    namespace: &str,
+    name: &str,
+) -> Option<&'a mut TypeDecl> {
+    for member in members {
+        match member {
+            NamespaceMember::Namespace(ns) => {
+                let nested = nested_namespace(namespace, ns);
+                if let Some(decl) = find_type_mut(&mut ns.members, &nested, name) {
+                    return Some(decl);
+                }
+            }
+            NamespaceMember::Type(decl) if qualified_name(namespace, decl) == name => {
+                return Some(decl)
+            }
+            NamespaceMember::Type(_) => {}
+        }
+    }
+    None
+}
+
+impl TypeDecl {
+    /// Folds another part of the same partial type into this one
+    fn absorb(&mut self, part: TypeDecl) {
+        // Only one part needs to spell out the accessibility or `abstract`
+        for modifier in part.modifiers {
+            if !self.modifiers.contains(&modifier) {
+                self.modifiers.push(modifier);
+            }
+        }
+        self.attributes.extend(part.attributes);
+        if self.doc_comment().is_none() {
+            self.trivia = part.trivia;
+        }
+        if self.parameters.is_none() {
+            self.parameters = part.parameters;
+        }
+        for base in part.base_types {
+            if !self.base_types.contains(&base) {
+                self.base_types.push(base);
+            }
+        }
+        for member in part.members {
+            match member {
+                MemberDecl::Type(nested) if nested.has_modifier("partial") => {
+                    let existing = self.members.iter_mut().find_map(|m| match m {
+                        MemberDecl::Type(own)
+                            if own.has_modifier("partial")
+                                && own.name == nested.name
+                                && own.type_parameters.len() == nested.type_parameters.len() =>
+                        {
+                            Some(own)
+                        }
+                        _ => None,
+                    });
+                    match existing {
+                        Some(own) => own.absorb(nested),
+                        None => self.members.push(MemberDecl::Type(nested)),
+                    }
+                }
+                member => self.members.push(member),
+            }
+        }
+    }
+}
+
+/// Replaces `using` alias names in every type reference with the aliased
+/// type. Aliases apply to their file or namespace; `global using` aliases
+/// apply across all units.
+pub fn resolve_using_aliases(units: &mut [CompilationUnit]) {
+    let global: HashMap<String, TypeSyntax> = units
+        .iter()
+        .flat_map(|unit| collect_aliases(&unit.usings, true))
+        .collect();
+
+    for unit in units {
+        let mut aliases = global.clone();
+        aliases.extend(collect_aliases(&unit.usings, false));
+        resolve_namespace_aliases(&mut unit.members, &aliases);
+    }
+}
+
+/// Records each declaration's full name and rewrites references to types
+/// declared in `units` to their full name. References resolve as in C#:
+/// through the enclosing types and namespaces, then the `using` directives
+/// in scope. Failing that, a simple name declared only once in the project
+/// is taken to mean that type, since project files may add global usings.
+/// Returns the references that stay ambiguous, with the types they may mean.
+pub fn resolve_type_references(units: &mut [CompilationUnit]) -> Vec<(String, Vec<String>)> {
+    let mut declared = HashSet::new();
+    for unit in units.iter_mut() {
+        assign_full_names(&mut unit.members, "", &mut declared);
+    }
+    let mut by_simple_name: HashMap<&str, Vec<&str>> = HashMap::new();
+    for full_name in &declared {
+        let simple = full_name.rsplit('.').next().unwrap_or(full_name);
+        by_simple_name.entry(simple).or_default().push(full_name);
+    }
+    let mut resolver = ReferenceResolver {
+        declared: &declared,
+        by_simple_name,
+        ambiguous: Vec::new(),
+    };
+
+    let global: Vec<String> = units
+        .iter()
+        .flat_map(|unit| collect_imports(&unit.usings, true))
+        .collect();
+    for unit in units {
+        let mut imports = collect_imports(&unit.usings, false);
+        imports.extend(global.iter().cloned());
+        resolver.resolve_namespace(&mut unit.members, "", &imports);
+    }
+    let mut ambiguous = resolver.ambiguous;
+    for (_, candidates) in &mut ambiguous {
+        candidates.sort();
+    }
+    ambiguous
+}
+
+fn assign_full_names(
+    members: &mut [NamespaceMember],
+    namespace: &str,
+    declared: &mut HashSet<String>,
+) {
+    for member in members {
+        match member {
+            NamespaceMember::Namespace(ns) => {
+                let nested = nested_namespace(namespace, ns);
+                assign_full_names(&mut ns.members, &nested, declared);
+            }
+            NamespaceMember::Type(decl) => decl.assign_full_name(namespace, declared),
+        }
+    }
+}
+
+impl TypeDecl {
+    fn assign_full_name(&mut self, container: &str, declared: &mut HashSet<String>) {
+        self.full_name = if container.is_empty() {
+            self.name.clone()
+        } else {
+            format!("{}.{}", container, self.name)
+        };
+        declared.insert(self.full_name.clone());
+        for member in &mut self.members {
+            if let MemberDecl::Type(nested) = member {
+                nested.assign_full_name(&self.full_name, declared);
+            }
+        }
+    }
+}
+
+/// Namespaces imported by plain `using` directives
+fn collect_imports(usings: &[UsingDirective], global: bool) -> Vec<String> {
+    usings
+        .iter()
+        .filter(|using| using.is_global == global && using.alias.is_none())
+        .map(|using| using.target.to_string())
+        .collect()
+}
+
+struct ReferenceResolver<'a> {
+    declared: &'a HashSet<String>,
+    by_simple_name: HashMap<&'a str, Vec<&'a str>>,
+    ambiguous: Vec<(String, Vec<String>)>,
+}
+
+impl ReferenceResolver<'_> {
+    fn resolve_namespace(
+        &mut self,
+        members: &mut [NamespaceMember],
+        namespace: &str,
+        imports: &[String],
+    ) {
+        for member in members {
+            match member {
+                NamespaceMember::Namespace(ns) => {
+                    let mut scoped = collect_imports(&ns.usings, false);
+                    scoped.extend(imports.iter().cloned());
+                    let nested = nested_namespace(namespace, ns);
+                    self.resolve_namespace(&mut ns.members, &nested, &scoped);
+                }
+                NamespaceMember::Type(decl) => self.resolve_type(decl, imports, &[]),
+            }
+        }
+    }
+
+    fn resolve_type(
+        &mut self,
+        decl: &mut TypeDecl,
+        imports: &[String],
+        outer_parameters: &[String],
+    ) {
+        // The type itself, its enclosing types and namespaces, innermost
+        // first, then the imported namespaces
+        let segments: Vec<&str> = decl.full_name.split('.').collect();
+        let scopes: Vec<String> = (0..=segments.len())
+            .rev()
+            .map(|length| segments[..length].join("."))
+            .chain(imports.iter().cloned())
+            .collect();
+        let mut type_parameters = outer_parameters.to_vec();
+        type_parameters.extend(decl.type_parameters.iter().cloned());
+
+        for param in decl.parameters.iter_mut().flatten() {
+            self.resolve(&mut param.type_name, &scopes, &type_parameters);
+        }
+        for base in &mut decl.base_types {
+            self.resolve(base, &scopes, &type_parameters);
+        }
+        for member in &mut decl.members {
+            match member {
+                MemberDecl::Property(property) => {
+                    self.resolve(&mut property.type_name, &scopes, &type_parameters)
+                }
+                MemberDecl::Field(field) => {
+                    self.resolve(&mut field.type_name, &scopes, &type_parameters)
+                }
+                MemberDecl::Type(nested) => self.resolve_type(nested, imports, &type_parameters),
+                MemberDecl::EnumMember(_) => {}
+            }
+        }
+    }
+
+    fn resolve(&mut self, syntax: &mut TypeSyntax, scopes: &[String], type_parameters: &[String]) {
+        match syntax {
+            TypeSyntax::Named {
+                qualifier,
+                name,
+                arguments,
+            } => {
+                for argument in arguments.iter_mut() {
+                    self.resolve(argument, scopes, type_parameters);
+                }
+                if qualifier.is_empty() && type_parameters.contains(name) {
+                    return;
+                }
+                let written: String = qualifier
+                    .iter()
+                    .map(|segment| format!("{}.", segment))
+                    .chain([name.clone()])
+                    .collect();
+                let found = scopes
+                    .iter()
+                    .map(|scope| match scope.as_str() {
+                        "" => written.clone(),
+                        scope => format!("{}.{}", scope, written),
+                    })
+                    .find(|candidate| self.declared.contains(candidate))
+                    .or_else(|| {
+                        let candidates: Vec<&str> = self
+                            .by_simple_name
+                            .get(name.as_str())
+                            .into_iter()
+                            .flatten()
+                            .copied()
+                            .filter(|full_name| full_name.ends_with(&format!(".{}", written)))
+                            .collect();
+                        match candidates.as_slice() {
+                            [only] => Some(only.to_string()),
+                            [] => None,
+                            many => {
+                                if !self.ambiguous.iter().any(|(w, _)| *w == written) {
+                                    self.ambiguous.push((
+                                        written.clone(),
+                                        many.iter().map(|c| c.to_string()).collect(),
+                                    ));
+                                }
+                                None
+                            }
+                        }
+                    });
+                if let Some(full_name) = found {
+                    let mut segments: Vec<String> =
+                        full_name.split('.').map(str::to_string).collect();
+                    segments.pop();
+                    *qualifier = segments;
+                }
+            }
+            TypeSyntax::Array(element, _)
+            | TypeSyntax::Nullable(element)
+            | TypeSyntax::Pointer(element) => self.resolve(element, scopes, type_parameters),
+            TypeSyntax::Tuple(elements) => {
+                for element in elements {
+                    self.resolve(&mut element.type_name, scopes, type_parameters);
+                }
+            }
+        }
+    }
+}
+
+/// Gives members outside any `#nullable` directive the project's nullable
+/// annotation setting
+pub fn apply_nullable_default(unit: &mut CompilationUnit, enabled: bool) {
+    fn apply(members: &mut [NamespaceMember], enabled: bool) {
+        for member in members {
+            match member {
+                NamespaceMember::Namespace(ns) => apply(&mut ns.members, enabled),
+                NamespaceMember::Type(decl) => decl.apply_nullable_default(enabled),
+            }
+        }
+    }
+    apply(&mut unit.members, enabled);
+}
+
+impl TypeDecl {
+    fn apply_nullable_default(&mut self, enabled: bool) {
+        for param in self.parameters.iter_mut().flatten() {
+            param.nullable_annotations.get_or_insert(enabled);
+        }
+        for member in &mut self.members {
+            match member {
+                MemberDecl::Property(property) => {
+                    property.nullable_annotations.get_or_insert(enabled);
+                }
+                MemberDecl::Type(nested) => nested.apply_nullable_default(enabled),
+                MemberDecl::Field(_) | MemberDecl::EnumMember(_) => {}
+            }
+        }
+    }
+}
+
+fn collect_aliases(usings: &[UsingDirective], global: bool) -> Vec<(String, TypeSyntax)> {
+    usings
+        .iter()
+        .filter(|using| using.is_global == global)
+        .filter_map(|using| Some((using.alias.clone()?, using.target.clone())))
+        .collect()
+}
+
+fn resolve_namespace_aliases(
+    members: &mut [NamespaceMember],
+    aliases: &HashMap<String, TypeSyntax>,
+) {
+    for member in members {
+        match member {
+            NamespaceMember::Namespace(ns) => {
+                let mut scoped = aliases.clone();
+                scoped.extend(collect_aliases(&ns.usings, false));
+                resolve_namespace_aliases(&mut ns.members, &scoped);
+            }
+            NamespaceMember::Type(decl) => decl.resolve_aliases(aliases),
+        }
+    }
+}
+
+impl TypeDecl {
+    fn resolve_aliases(&mut self, aliases: &HashMap<String, TypeSyntax>) {
+        for param in self.parameters.iter_mut().flatten() {
+            param.type_name.resolve_aliases(aliases);
+        }
+        for base in &mut self.base_types {
+            base.resolve_aliases(aliases);
+        }
+        for member in &mut self.members {
+            match member {
+                MemberDecl::Property(property) => property.type_name.resolve_aliases(aliases),
+                MemberDecl::Field(field) => field.type_name.resolve_aliases(aliases),
+                MemberDecl::Type(nested) => nested.resolve_aliases(aliases),
+                MemberDecl::EnumMember(_) => {}
+            }
+        }
+    }
+}
+
+impl TypeSyntax {
+    fn resolve_aliases(&mut self, aliases: &HashMap<String, TypeSyntax>) {
+        match self {
+            TypeSyntax::Named {
+                qualifier,
+                name,
+                arguments,
+            } => {
+                for argument in arguments.iter_mut() {
+                    argument.resolve_aliases(aliases);
+                }
+                if qualifier.is_empty() && arguments.is_empty() {
+                    if let Some(target) = aliases.get(name.as_str()) {
+                        *self = target.clone();
+                    }
+                    return;
+                }
+                // A namespace alias such as `using Models = App.Models;`
+                // qualifying a name
+                if let Some(TypeSyntax::Named {
+                    qualifier: outer,
+                    name: last,
+                    arguments: alias_arguments,
+                }) = qualifier.first().and_then(|first| aliases.get(first))
+                {
+                    if alias_arguments.is_empty() {
+                        let mut expanded = outer.clone();
+                        expanded.push(last.clone());
+                        expanded.extend(qualifier.drain(1..));
+                        *qualifier = expanded;
+                    }
+                }
+            }
+            TypeSyntax::Array(element, _)
+            | TypeSyntax::Nullable(element)
+            | TypeSyntax::Pointer(element) => element.resolve_aliases(aliases),
+            TypeSyntax::Tuple(elements) => {
+                for element in elements {
+                    element.type_name.resolve_aliases(aliases);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+
+    fn parse_all(sources: &[&str]) -> Vec<CompilationUnit> {
+        sources
+            .iter()
+            .map(|source| parse(source, &[]).expect("source should parse"))
+            .collect()
+    }
+
+    fn property_names(decl: &TypeDecl) -> Vec<&str> {
+        decl.members
+            .iter()
+            .filter_map(|member| match member {
+                MemberDecl::Property(p) => Some(p.name.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn merge_partial_types_moves_parts_into_the_first_declaration() {
+        let mut units = parse_all(&[
+            "namespace App; public partial class Dto { public int A { get; set; } }",
+            "namespace App { public partial class Dto : BaseDto { public int B { get; set; } } public class Other { } }",
+            "namespace App.Inner; public partial class Dto { public int C { get; set; } }",
+            "namespace App; public partial class Dto { public int D { get; set; } }",
+        ]);
+
+        merge_partial_types(&mut units);
+
+        let owner = units[0].types()[0];
+        assert_eq!(property_names(owner), ["A", "B", "D"]);
+        assert_eq!(owner.base_types[0].to_string(), "BaseDto");
+        let names: Vec<&str> = units[1].types().iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["Other"]);
+        assert_eq!(property_names(units[2].types()[0]), ["C"]);
+        assert!(units[3].types().is_empty());
+    }
+
+    fn property_types(decl: &TypeDecl) -> Vec<String> {
+        decl.members
+            .iter()
+            .filter_map(|member| match member {
+                MemberDecl::Property(p) => Some(p.type_name.to_string()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn resolve_using_aliases_follows_alias_scopes() {
+        let mut units = parse_all(&[
+            "global using StudentId = System.Guid;",
+            r#"
+            using Scores = System.Collections.Generic.Dictionary<string, int>;
+            namespace App
+            {
+                using Tags = System.Collections.Generic.List<string>;
+                public class StudentDto
+                {
+                    public StudentId Id { get; set; }
+                    public Scores Scores { get; set; }
+                    public Tags Tags { get; set; }
+                    public List<StudentId> Friends { get; set; }
+                }
+            }
+            "#,
+            "public class OtherDto { public StudentId Id { get; set; } public Scores Scores { get; set; } }",
+        ]);
+
+        resolve_using_aliases(&mut units);
+
+        assert_eq!(
+            property_types(units[1].types()[0]),
+            [
+                "System.Guid",
+                "System.Collections.Generic.Dictionary<string, int>",
+                "System.Collections.Generic.List<string>",
+                "List<System.Guid>",
+            ]
+        );
+        assert_eq!(
+            property_types(units[2].types()[0]),
+            ["System.Guid", "Scores"]
+        );
+    }
+
+    #[test]
+    fn split_by_namespace_keeps_usings_and_enclosing_namespaces() {
+        let unit = parse(
+            r#"
+            using System;
+            namespace App
+            {
+                namespace Students { public class StudentDto { } }
+                public enum Status { Active }
+                namespace Students { public class GradeDto { } }
+            }
+            namespace Other { using App.Students; public class TeacherDto { } }
+            "#,
+            &[],
+        )
+        .expect("source should parse");
+
+        let parts = unit.split_by_namespace();
+
+        let summary: Vec<(Option<String>, Vec<&str>, usize)> = parts
+            .iter()
+            .map(|part| {
+                let names = part.types().iter().map(|t| t.name.as_str()).collect();
+                (part.namespace(), names, part.usings.len())
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (
+                    Some("App.Students".to_string()),
+                    vec!["StudentDto", "GradeDto"],
+                    1
+                ),
+                (Some("App".to_string()), vec!["Status"], 1),
+                (Some("Other".to_string()), vec!["TeacherDto"], 1),
+            ]
+        );
+        match &parts[2].members[0] {
+            NamespaceMember::Namespace(ns) => assert_eq!(ns.usings.len(), 1),
+            NamespaceMember::Type(_) => panic!("TeacherDto should stay inside its namespace"),
+        }
+    }
+
+    #[test]
+    fn resolve_type_references_follow_scopes_and_usings() {
+        let mut units = parse_all(&[
+            "namespace App.Grades; public enum Status { Active } public class Course { public class Section { } }",
+            "namespace App.Billing; public enum Status { Paid } public class Invoice { }",
+            "namespace Vendor.Money; public class Amount { }",
+            r#"
+            using App.Billing;
+            namespace App.Grades.Reports
+            {
+                public class ReportDto<T>
+                {
+                    public Status Status { get; set; }
+                    public Invoice Invoice { get; set; }
+                    public Course.Section Section { get; set; }
+                    public Billing.Status Billing { get; set; }
+                    public List<Amount> Amounts { get; set; }
+                    public T Item { get; set; }
+                    public Missing Missing { get; set; }
+                }
+            }
+            "#,
+        ]);
+
+        let ambiguous = resolve_type_references(&mut units);
+
+        assert!(ambiguous.is_empty());
+        let report = units[3].types()[0];
+        assert_eq!(report.full_name, "App.Grades.Reports.ReportDto");
+        assert_eq!(
+            property_types(report),
+            [
+                "App.Grades.Status",
+                "App.Billing.Invoice",
+                "App.Grades.Course.Section",
+                "App.Billing.Status",
+                "List<Vendor.Money.Amount>",
+                "T",
+                "Missing",
+            ]
+        );
+    }
+
+    #[test]
+    fn resolve_type_references_reports_ambiguous_names() {
+        let mut units = parse_all(&[
+            "namespace App.Grades; public enum Status { Active }",
+            "namespace App.Billing; public enum Status { Paid }",
+            "namespace App.Reports; public class ReportDto { public Status First { get; set; } public Status Second { get; set; } }",
+        ]);
+
+        let ambiguous = resolve_type_references(&mut units);
+
+        assert_eq!(
+            ambiguous,
+            [(
+                "Status".to_string(),
+                vec![
+                    "App.Billing.Status".to_string(),
+                    "App.Grades.Status".to_string()
+                ]
+            )]
+        );
+        assert_eq!(property_types(units[2].types()[0]), ["Status", "Status"]);
+    }
+}
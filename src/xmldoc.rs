@@ -0,0 +1,552 @@
+//! Converts C# XML documentation comments to JSDoc
+
+#[derive(Debug, Clone)]
+enum Node {
+    Text(String),
+    Element {
+        name: String,
+        attributes: Vec<(String, String)>,
+        children: Vec<Node>,
+    },
+}
+
+/// The parts of an XML doc comment that map onto JSDoc
+#[derive(Debug, Clone, Default)]
+pub struct XmlDoc {
+    pub summary: String,
+    pub remarks: Option<String>,
+    pub value: Option<String>,
+    pub params: Vec<(String, String)>,
+    pub type_params: Vec<(String, String)>,
+    pub returns: Option<String>,
+    pub exceptions: Vec<(String, String)>,
+    pub examples: Vec<String>,
+    pub see_also: Vec<String>,
+    /// Message of an `[Obsolete]` attribute on the declaration
+    pub deprecated: Option<String>,
+    /// Set by `<inheritdoc/>`
+    pub inherits: bool,
+    /// The `cref` of `<inheritdoc cref="..."/>`, when it names a source
+    pub inherit_cref: Option<String>,
+}
+
+impl XmlDoc {
+    /// Parses the text of the `///` lines, with the slashes already removed
+    pub fn parse(comment: &str) -> Self {
+        let mut doc = Self::default();
+        for node in parse_nodes(comment) {
+            let Node::Element {
+                name,
+                attributes,
+                children,
+            } = &node
+            else {
+                // Bare text without a `<summary>` is still worth keeping
+                let text = render(std::slice::from_ref(&node));
+                append_paragraph(&mut doc.summary, &text);
+                continue;
+            };
+            let attribute = |key: &str| {
+                attributes
+                    .iter()
+                    .find(|(k, _)| k == key)
+                    .map(|(_, v)| v.clone())
+            };
+            let text = render(children);
+            match name.as_str() {
+                "summary" => append_paragraph(&mut doc.summary, &text),
+                "remarks" => doc.remarks = Some(text),
+                "value" => doc.value = Some(text),
+                "returns" => doc.returns = Some(text),
+                "example" => doc.examples.push(text),
+                "param" => doc
+                    .params
+                    .push((attribute("name").unwrap_or_default(), text)),
+                "typeparam" => doc
+                    .type_params
+                    .push((attribute("name").unwrap_or_default(), text)),
+                "exception" => doc.exceptions.push((
+                    attribute("cref")
+                        .map(|c| link_target(&c))
+                        .unwrap_or_default(),
+                    text,
+                )),
+                "seealso" => doc.see_also.push(render(std::slice::from_ref(&node))),
+                "inheritdoc" => {
+                    doc.inherits = true;
+                    doc.inherit_cref = attribute("cref").map(|c| link_target(&c));
+                }
+                _ => append_paragraph(&mut doc.summary, &render(std::slice::from_ref(&node))),
+            }
+        }
+        doc
+    }
+
+    /// Fills in whatever this comment leaves out from the one it inherits
+    pub fn inherit_from(&mut self, base: &XmlDoc) {
+        if self.summary.is_empty() {
+            self.summary = base.summary.clone();
+        }
+        for (own, inherited) in [
+            (&mut self.remarks, &base.remarks),
+            (&mut self.value, &base.value),
+            (&mut self.returns, &base.returns),
+        ] {
+            if own.is_none() {
+                own.clone_from(inherited);
+            }
+        }
+        for (own, inherited) in [
+            (&mut self.params, &base.params),
+            (&mut self.type_params, &base.type_params),
+            (&mut self.exceptions, &base.exceptions),
+        ] {
+            for (name, text) in inherited {
+                if !own.iter().any(|(n, _)| n == name) {
+                    own.push((name.clone(), text.clone()));
+                }
+            }
+        }
+        if self.examples.is_empty() {
+            self.examples.clone_from(&base.examples);
+        }
+        if self.see_also.is_empty() {
+            self.see_also.clone_from(&base.see_also);
+        }
+        self.inherits = false;
+    }
+
+    /// JSDoc body, one comment line per line, or `None` if there is nothing
+    /// to say
+    pub fn to_jsdoc(&self) -> Option<String> {
+        let mut description = self.summary.clone();
+        if let Some(value) = &self.value {
+            append_paragraph(&mut description, value);
+        }
+
+        let mut tags = Vec::new();
+        if let Some(message) = &self.deprecated {
+            tags.push(tag("@deprecated", message));
+        }
+        if let Some(remarks) = &self.remarks {
+            tags.push(tag("@remarks", remarks));
+        }
+        for (name, text) in &self.type_params {
+            tags.push(tag(&format!("@template {}", name), text));
+        }
+        for (name, text) in &self.params {
+            tags.push(tag(&format!("@param {}", name), text));
+        }
+        if let Some(returns) = &self.returns {
+            tags.push(tag("@returns", returns));
+        }
+        for (cref, text) in &self.exceptions {
+            tags.push(tag(&format!("@throws {{{}}}", cref), text));
+        }
+        for example in &self.examples {
+            tags.push(format!("@example\n{}", example));
+        }
+        for see in &self.see_also {
+            tags.push(tag("@see", see));
+        }
+
+        let mut sections = Vec::new();
+        if !description.is_empty() {
+            sections.push(description);
+        }
+        if !tags.is_empty() {
+            sections.push(tags.join("\n"));
+        }
+        if sections.is_empty() {
+            None
+        } else {
+            Some(sections.join("\n\n"))
+        }
+    }
+}
+
+fn tag(name: &str, text: &str) -> String {
+    if text.is_empty() {
+        name.to_string()
+    } else {
+        format!("{} {}", name, text)
+    }
+}
+
+fn append_paragraph(target: &mut String, text: &str) {
+    if text.is_empty() {
+        return;
+    }
+    if !target.is_empty() {
+        target.push_str("\n\n");
+    }
+    target.push_str(text);
+}
+
+/// Name, attributes and children of an element whose end tag is pending
+type OpenElement = (String, Vec<(String, String)>, Vec<Node>);
+
+/// Lenient XML reader: unknown or unbalanced markup degrades to text
+fn parse_nodes(source: &str) -> Vec<Node> {
+    let mut stack: Vec<OpenElement> = vec![(String::new(), Vec::new(), Vec::new())];
+    let mut rest = source;
+
+    while !rest.is_empty() {
+        let Some(open) = rest.find('<') else {
+            push_text(&mut stack, rest);
+            break;
+        };
+        push_text(&mut stack, &rest[..open]);
+        rest = &rest[open..];
+        // A `<` that can't start a tag, as in `1 < 2`, is plain text
+        if !rest[1..].starts_with(|c: char| c.is_alphabetic() || matches!(c, '/' | '!' | '?')) {
+            push_text(&mut stack, "<");
+            rest = &rest[1..];
+            continue;
+        }
+        let Some(close) = rest.find('>') else {
+            push_text(&mut stack, rest);
+            break;
+        };
+        let tag = &rest[1..close];
+        rest = &rest[close + 1..];
+
+        if let Some(name) = tag.strip_prefix('/') {
+            let name = name.trim();
+            // Stray end tags are dropped; the root element is never closed
+            if let Some(depth) = stack.iter().skip(1).rposition(|(n, ..)| n == name) {
+                let depth = depth + 1;
+                while stack.len() > depth {
+                    let (name, attributes, children) = stack.pop().unwrap();
+                    stack.last_mut().unwrap().2.push(Node::Element {
+                        name,
+                        attributes,
+                        children,
+                    });
+                }
+            }
+            continue;
+        }
+        if tag.starts_with('!') || tag.starts_with('?') {
+            continue;
+        }
+
+        let self_closing = tag.ends_with('/');
+        let tag = tag.trim_end_matches('/');
+        let name_end = tag.find(char::is_whitespace).unwrap_or(tag.len());
+        let name = tag[..name_end].to_string();
+        let attributes = parse_attributes(&tag[name_end..]);
+        if self_closing {
+            stack.last_mut().unwrap().2.push(Node::Element {
+                name,
+                attributes,
+                children: Vec::new(),
+            });
+        } else {
+            stack.push((name, attributes, Vec::new()));
+        }
+    }
+
+    // Close anything left open at the end of the comment
+    while stack.len() > 1 {
+        let (name, attributes, children) = stack.pop().unwrap();
+        stack.last_mut().unwrap().2.push(Node::Element {
+            name,
+            attributes,
+            children,
+        });
+    }
+    stack.pop().unwrap().2
+}
+
+fn push_text(stack: &mut [OpenElement], text: &str) {
+    if !text.is_empty() {
+        stack
+            .last_mut()
+            .unwrap()
+            .2
+            .push(Node::Text(decode_entities(text)));
+    }
+}
+
+fn parse_attributes(source: &str) -> Vec<(String, String)> {
+    let mut attributes = Vec::new();
+    let mut rest = source.trim();
+    while let Some(eq) = rest.find('=') {
+        let key = rest[..eq].trim().to_string();
+        let value = rest[eq + 1..].trim_start();
+        let Some(quote) = value.chars().next().filter(|c| *c == '"' || *c == '\'') else {
+            break;
+        };
+        let value = &value[1..];
+        let end = value.find(quote).unwrap_or(value.len());
+        attributes.push((key, decode_entities(&value[..end])));
+        rest = value.get(end + 1..).unwrap_or("").trim_start();
+    }
+    attributes
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// `T:App.Models.UserDto` becomes `UserDto`, `P:App.Models.UserDto.Name`
+/// becomes `UserDto.Name`; generic arities and parameter lists are dropped
+fn link_target(cref: &str) -> String {
+    let (kind, name) = match cref.split_once(':') {
+        Some((kind, name)) if kind.len() == 1 => (Some(kind), name),
+        _ => (None, cref),
+    };
+    let name = name.split('(').next().unwrap_or(name);
+    let name: String = {
+        let mut depth = 0;
+        name.chars()
+            .filter(|c| {
+                match c {
+                    '{' | '<' => depth += 1,
+                    '}' | '>' => depth -= 1,
+                    _ => return depth == 0,
+                }
+                false
+            })
+            .collect()
+    };
+    let name = name
+        .split('.')
+        .map(|segment| segment.split('`').next().unwrap_or(segment))
+        .collect::<Vec<_>>();
+    let keep = match kind {
+        Some("T") => 1,
+        Some(_) => 2,
+        None => name.len(),
+    };
+    name[name.len().saturating_sub(keep)..].join(".")
+}
+
+/// Renders mixed content as Markdown-flavoured JSDoc text
+fn render(nodes: &[Node]) -> String {
+    let mut output = String::new();
+    render_into(nodes, &mut output);
+    normalize(&output)
+}
+
+fn render_into(nodes: &[Node], output: &mut String) {
+    for node in nodes {
+        match node {
+            Node::Text(text) => {
+                for (i, word) in text.split_whitespace().enumerate() {
+                    let joins_previous = i == 0 && !text.starts_with(char::is_whitespace);
+                    if !joins_previous && !output.is_empty() && !output.ends_with(['\n', ' ']) {
+                        output.push(' ');
+                    }
+                    output.push_str(word);
+                }
+                if text.ends_with(char::is_whitespace) && !text.trim().is_empty() {
+                    output.push(' ');
+                }
+            }
+            Node::Element {
+                name,
+                attributes,
+                children,
+            } => {
+                let attribute = |key: &str| {
+                    attributes
+                        .iter()
+                        .find(|(k, _)| k == key)
+                        .map(|(_, v)| v.as_str())
+                };
+                match name.as_str() {
+                    "see" | "seealso" => {
+                        let mut label = String::new();
+                        render_into(children, &mut label);
+                        let label = label.trim();
+                        let link = if let Some(cref) = attribute("cref") {
+                            Some(link_target(cref))
+                        } else {
+                            attribute("href").map(str::to_string)
+                        };
+                        match (link, attribute("langword")) {
+                            (Some(link), _) if label.is_empty() => {
+                                output.push_str(&format!("{{@link {}}}", link))
+                            }
+                            (Some(link), _) => {
+                                output.push_str(&format!("{{@link {} | {}}}", link, label))
+                            }
+                            (None, Some(word)) => output.push_str(&format!("`{}`", word)),
+                            (None, None) => output.push_str(label),
+                        }
+                    }
+                    "paramref" | "typeparamref" => {
+                        output.push_str(&format!("`{}`", attribute("name").unwrap_or_default()))
+                    }
+                    "c" => {
+                        let mut code = String::new();
+                        render_into(children, &mut code);
+                        output.push_str(&format!("`{}`", code.trim()));
+                    }
+                    "code" => {
+                        output.push_str("\n```\n");
+                        output.push_str(&raw_code(children));
+                        output.push_str("\n```\n");
+                    }
+                    "para" => {
+                        output.push_str("\n\n");
+                        render_into(children, output);
+                        output.push_str("\n\n");
+                    }
+                    "br" => output.push('\n'),
+                    "list" => render_list(attribute("type"), children, output),
+                    _ => render_into(children, output),
+                }
+            }
+        }
+    }
+}
+
+fn render_list(list_type: Option<&str>, items: &[Node], output: &mut String) {
+    output.push('\n');
+    let mut number = 0;
+    for item in items {
+        let Node::Element { name, children, .. } = item else {
+            continue;
+        };
+        if name != "item" && name != "listheader" {
+            continue;
+        }
+        let part = |part: &str| {
+            children.iter().find_map(|child| match child {
+                Node::Element { name, children, .. } if name == part => Some(render(children)),
+                _ => None,
+            })
+        };
+        let text = match (part("term"), part("description")) {
+            (Some(term), Some(description)) => format!("{} — {}", term, description),
+            (Some(term), None) => term,
+            (None, Some(description)) => description,
+            (None, None) => render(children),
+        };
+        let text = text.replace('\n', " ");
+        if name == "listheader" {
+            output.push_str(&format!("{}\n", text));
+        } else if list_type == Some("number") {
+            number += 1;
+            output.push_str(&format!("{}. {}\n", number, text));
+        } else {
+            output.push_str(&format!("- {}\n", text));
+        }
+    }
+    output.push('\n');
+}
+
+/// Code blocks keep their lines, minus the indentation they share
+fn raw_code(nodes: &[Node]) -> String {
+    let mut text = String::new();
+    for node in nodes {
+        match node {
+            Node::Text(t) => text.push_str(t),
+            Node::Element { children, .. } => text.push_str(&raw_code(children)),
+        }
+    }
+    let lines: Vec<&str> = text.lines().collect();
+    let first = lines.iter().position(|l| !l.trim().is_empty()).unwrap_or(0);
+    let last = lines
+        .iter()
+        .rposition(|l| !l.trim().is_empty())
+        .map_or(0, |i| i + 1);
+    let lines = &lines[first..last.max(first)];
+    let indent = lines
+        .iter()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| l.len() - l.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    lines
+        .iter()
+        .map(|l| l.get(indent..).unwrap_or("").trim_end())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Trims lines and collapses blank runs, leaving code fences untouched
+fn normalize(text: &str) -> String {
+    let mut lines: Vec<&str> = Vec::new();
+    let mut in_code = false;
+    for line in text.lines() {
+        if line.trim() == "```" {
+            in_code = !in_code;
+            lines.push("```");
+        } else if in_code {
+            lines.push(line);
+        } else {
+            let line = line.trim();
+            if !line.is_empty() || lines.last().is_some_and(|l| !l.is_empty()) {
+                lines.push(line);
+            }
+        }
+    }
+    while lines.last().is_some_and(|l| l.is_empty()) {
+        lines.pop();
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_with_inline_tags() {
+        let doc = XmlDoc::parse(
+            " <summary>\n Gets the <see cref=\"T:App.Models.UserDto\"/> for <paramref name=\"id\"/>,\n or <see langword=\"null\"/>.\n </summary>",
+        );
+        assert_eq!(doc.summary, "Gets the {@link UserDto} for `id`, or `null`.");
+    }
+
+    #[test]
+    fn multi_line_tags_become_jsdoc_tags() {
+        let doc = XmlDoc::parse(
+            " <summary>Pages results</summary>\n <param name=\"size\">Items per\n page</param>\n <returns>The\n page</returns>\n <exception cref=\"ArgumentException\">When size is 0</exception>",
+        );
+        assert_eq!(
+            doc.to_jsdoc().as_deref(),
+            Some(
+                "Pages results\n\n@param size Items per page\n@returns The page\n@throws {ArgumentException} When size is 0"
+            )
+        );
+    }
+
+    #[test]
+    fn code_blocks_and_lists() {
+        let doc = XmlDoc::parse(
+            " <summary>Steps<list type=\"number\"><item><term>One</term><description>first</description></item><item>Two</item></list></summary>\n <example><code>\n   var x = 1;\n     x++;\n </code></example>",
+        );
+        assert_eq!(doc.summary, "Steps\n1. One — first\n2. Two");
+        assert_eq!(doc.examples, ["```\nvar x = 1;\n  x++;\n```"]);
+    }
+
+    #[test]
+    fn inheritdoc_fills_missing_parts() {
+        let mut doc = XmlDoc::parse(
+            " <inheritdoc cref=\"M:App.Base.Run(System.Int32)\"/>\n <remarks>Own</remarks>",
+        );
+        assert!(doc.inherits);
+        assert_eq!(doc.inherit_cref.as_deref(), Some("Base.Run"));
+        doc.inherit_from(&XmlDoc::parse(
+            " <summary>Base</summary>\n <remarks>Base remarks</remarks>",
+        ));
+        assert!(!doc.inherits);
+        assert_eq!(doc.summary, "Base");
+        assert_eq!(doc.remarks.as_deref(), Some("Own"));
+    }
+
+    #[test]
+    fn unbalanced_markup_degrades_to_text() {
+        let doc = XmlDoc::parse(" <summary>Uses a &lt;T&gt; and 1 < 2</summary>");
+        assert_eq!(doc.summary, "Uses a <T> and 1 < 2");
+    }
+}